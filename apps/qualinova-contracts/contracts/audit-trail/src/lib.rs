@@ -39,6 +39,8 @@ mod types {
         AuditRecordsByType(OperationType), // Use u32 as OperationType
         AuditRecordsBySubject(String), // subject_id
         LastAuditId, // To store the last assigned ID number
+        Admin, // Address allowed to upgrade the contract
+        SchemaVersion, // Version of the storage layout
     }
 
     #[derive(Clone, Debug, Eq, PartialEq)]
@@ -60,6 +62,10 @@ mod constants {
     pub const OPERATION_VERIFICATION: OperationType = 2;
     pub const OPERATION_REVOCATION: OperationType = 3;
     pub const OPERATION_ACCESS: OperationType = 4;
+
+    // Version of the storage layout this build reads and writes
+    pub const SCHEMA_VERSION: u32 = 1;
+    pub const UNVERSIONED_SCHEMA_VERSION: u32 = 1;
 }
 
 // MODULE: Utilities
@@ -540,17 +546,61 @@ impl AuditTrailContract {
     pub fn get_by_type(env: Env, operation_type: OperationType) -> Vec<AuditRecord> {
         Self::get_audits_by_type(env, operation_type)
     }
+
+    /// Set the admin allowed to upgrade the contract as part of the deployment,
+    /// so nobody can claim a new deployment before its deployer
+    pub fn __constructor(env: Env, admin: Address) {
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::SchemaVersion, &SCHEMA_VERSION);
+    }
+
+    /// Replace the contract's code with an uploaded Wasm, keeping the audit records
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
+        Self::require_admin(&env);
+        env.deployer().update_current_contract_wasm(new_wasm_hash);
+    }
+
+    /// Bring the stored schema version up to this build's; audit records need no rewriting
+    pub fn migrate(env: Env) -> u32 {
+        Self::require_admin(&env);
+
+        if Self::get_schema_version(env.clone()) > SCHEMA_VERSION {
+            panic!("Stored data has a newer schema version");
+        }
+
+        env.storage().instance().set(&DataKey::SchemaVersion, &SCHEMA_VERSION);
+        SCHEMA_VERSION
+    }
+
+    /// Get the schema version of the stored data
+    pub fn get_schema_version(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::SchemaVersion)
+            .unwrap_or(UNVERSIONED_SCHEMA_VERSION)
+    }
+}
+
+impl AuditTrailContract {
+    fn require_admin(env: &Env) {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .unwrap_or_else(|| panic!("Admin not set"));
+        admin.require_auth();
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use soroban_sdk::{testutils::Address as _, Env, Symbol, IntoVal};
+    use soroban_sdk::{testutils::Address as _, Bytes, Env, Symbol, IntoVal};
 
     #[test]
     fn test_record_audit() {
         let env = Env::default();
-        let contract_id = env.register_contract(None, AuditTrailContract);
+        let contract_id = env.register(AuditTrailContract, (Address::generate(&env),));
 
         let user = Address::generate(&env);
         let operation_type = 1; // UPDATE
@@ -592,7 +642,7 @@ mod test {
     #[test]
     fn test_by_type() {
         let env = Env::default();
-        let contract_id = env.register_contract(None, AuditTrailContract);
+        let contract_id = env.register(AuditTrailContract, (Address::generate(&env),));
 
         let user = Address::generate(&env);
         let tx_hash = BytesN::from_array(&env, &[0; 32]);
@@ -639,7 +689,7 @@ mod test {
     #[test]
     fn test_audit_trail() {
         let env = Env::default();
-        let contract_id = env.register_contract(None, AuditTrailContract);
+        let contract_id = env.register(AuditTrailContract, (Address::generate(&env),));
 
         let user = Address::generate(&env);
         let tx_hash = BytesN::from_array(&env, &[0; 32]);
@@ -718,7 +768,7 @@ mod test {
     #[test]
     fn test_timeframe() {
         let env = Env::default();
-        let contract_id = env.register_contract(None, AuditTrailContract);
+        let contract_id = env.register(AuditTrailContract, (Address::generate(&env),));
 
         let user = Address::generate(&env);
         let tx_hash = BytesN::from_array(&env, &[0; 32]);
@@ -757,7 +807,7 @@ mod test {
     #[test]
     fn test_search() {
         let env = Env::default();
-        let contract_id = env.register_contract(None, AuditTrailContract);
+        let contract_id = env.register(AuditTrailContract, (Address::generate(&env),));
 
         let user = Address::generate(&env);
         let tx_hash = BytesN::from_array(&env, &[0; 32]);
@@ -853,7 +903,7 @@ mod test {
     #[test]
     fn test_export() {
         let env = Env::default();
-        let contract_id = env.register_contract(None, AuditTrailContract);
+        let contract_id = env.register(AuditTrailContract, (Address::generate(&env),));
 
         let user = Address::generate(&env);
         let tx_hash = BytesN::from_array(&env, &[0; 32]);
@@ -919,4 +969,55 @@ mod test {
         assert!(pdf_export.contains_key(String::from_str(&env, "format")));
         assert_eq!(pdf_export.get(String::from_str(&env, "format")).unwrap(), String::from_str(&env, "pdf"));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_upgrade_and_migrate() {
+        let env = Env::default();
+        let admin = Address::generate(&env);
+        let contract_id = env.register(AuditTrailContract, (admin.clone(),));
+
+        env.mock_all_auths();
+
+        // A new deployment starts at the current schema version
+        let version_fn = Symbol::new(&env, "get_schema_version");
+        let version: u32 = env.invoke_contract(&contract_id, &version_fn, ().into_val(&env));
+        assert_eq!(version, SCHEMA_VERSION);
+
+        // Deployments from before schema versioning are migrated to the current version
+        env.as_contract(&contract_id, || {
+            env.storage().instance().set(&DataKey::SchemaVersion, &0u32);
+        });
+        let migrate_fn = Symbol::new(&env, "migrate");
+        let migrated: u32 = env.invoke_contract(&contract_id, &migrate_fn, ().into_val(&env));
+        assert_eq!(migrated, SCHEMA_VERSION);
+        let version: u32 = env.invoke_contract(&contract_id, &version_fn, ().into_val(&env));
+        assert_eq!(version, SCHEMA_VERSION);
+
+        // A test contract runs the empty Wasm, so upgrading to it succeeds, but
+        // only with the admin's authorization
+        let upgrade_fn = Symbol::new(&env, "upgrade");
+        let wasm_hash: BytesN<32> = env.crypto().sha256(&Bytes::new(&env)).into();
+        env.set_auths(&[]);
+        let result = env.try_invoke_contract::<(), soroban_sdk::Error>(&contract_id, &upgrade_fn, (wasm_hash.clone(),).into_val(&env));
+        assert!(result.is_err());
+        env.mock_all_auths();
+        let _: () = env.invoke_contract(&contract_id, &upgrade_fn, (wasm_hash,).into_val(&env));
+        assert_eq!(env.auths()[0].0, admin);
+    }
+
+    #[test]
+    #[should_panic(expected = "Stored data has a newer schema version")]
+    fn test_migrate_newer_schema() {
+        let env = Env::default();
+        let contract_id = env.register(AuditTrailContract, (Address::generate(&env),));
+
+        env.mock_all_auths();
+
+        env.as_contract(&contract_id, || {
+            env.storage().instance().set(&DataKey::SchemaVersion, &(SCHEMA_VERSION + 1));
+        });
+
+        let migrate_fn = Symbol::new(&env, "migrate");
+        let _: u32 = env.invoke_contract(&contract_id, &migrate_fn, ().into_val(&env));
+    }
+}
@@ -1,5 +1,9 @@
 use crate::types::*;
-use soroban_sdk::{Address, Env, Vec};
+use soroban_sdk::{Address, BytesN, Env, Vec};
+
+// Version of the storage layout this build reads and writes
+pub const SCHEMA_VERSION: u32 = 1;
+const UNVERSIONED_SCHEMA_VERSION: u32 = 1;
 
 pub fn initialize(env: Env, admin: Address) {
     admin.require_auth();
@@ -15,3 +19,42 @@ pub fn initialize(env: Env, admin: Address) {
         .instance()
         .set(&DataKey::NextAuthorityId, &1u32);
 }
+
+fn require_admin(env: &Env) -> Address {
+    let admin: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .unwrap_or_else(|| panic!("Admin not set"));
+    admin.require_auth();
+    admin
+}
+
+pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
+    require_admin(&env);
+    env.deployer().update_current_contract_wasm(new_wasm_hash);
+}
+
+pub fn migrate(env: Env) -> u32 {
+    require_admin(&env);
+
+    if get_schema_version(env.clone()) > SCHEMA_VERSION {
+        panic!("Stored data has a newer schema version");
+    }
+
+    set_schema_version(&env);
+    SCHEMA_VERSION
+}
+
+pub fn get_schema_version(env: Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::SchemaVersion)
+        .unwrap_or(UNVERSIONED_SCHEMA_VERSION)
+}
+
+pub fn set_schema_version(env: &Env) {
+    env.storage()
+        .instance()
+        .set(&DataKey::SchemaVersion, &SCHEMA_VERSION);
+}
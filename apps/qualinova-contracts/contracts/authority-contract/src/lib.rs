@@ -1,5 +1,5 @@
 #![no_std]
-use soroban_sdk::{Address, Env, String, Vec, contract, contractimpl};
+use soroban_sdk::{Address, BytesN, Env, String, Vec, contract, contractimpl};
 
 mod admin;
 mod authority;
@@ -21,6 +21,7 @@ impl CertifyingAuthority {
             panic!("Contract already initialized");
         }
         env.storage().instance().set(&DataKey::Admin, &admin);
+        admin::set_schema_version(&env);
     }
     // Authority management functions
     pub fn register_authority(
@@ -62,4 +63,17 @@ impl CertifyingAuthority {
     pub fn get_authority(env: Env, authority_id: Address) -> Authority {
         authority::get_authority(env, authority_id)
     }
+
+    // Upgrade functions
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
+        admin::upgrade(env, new_wasm_hash)
+    }
+
+    pub fn migrate(env: Env) -> u32 {
+        admin::migrate(env)
+    }
+
+    pub fn get_schema_version(env: Env) -> u32 {
+        admin::get_schema_version(env)
+    }
 }
@@ -2,8 +2,8 @@
 extern crate std;
 
 use crate::types::*;
-use crate::{CertifyingAuthority, CertifyingAuthorityClient};
-use soroban_sdk::{Address, Env, String, testutils::Address as _, vec};
+use crate::{CertifyingAuthority, CertifyingAuthorityClient, SCHEMA_VERSION};
+use soroban_sdk::{Address, Bytes, BytesN, Env, String, testutils::Address as _, vec};
 
 #[test]
 fn test_initialize_contract() {
@@ -464,3 +464,50 @@ fn test_register_authority_after_deactivation() {
         &cert_types,
     );
 }
+
+#[test]
+fn test_upgrade_and_migrate() {
+    let env = Env::default();
+    let contract_address = env.register(CertifyingAuthority, ());
+    let contract_client = CertifyingAuthorityClient::new(&env, &contract_address);
+
+    let admin = Address::generate(&env);
+    env.mock_all_auths();
+    contract_client.initialize(&admin);
+
+    // A new deployment starts at the current schema version
+    assert_eq!(contract_client.get_schema_version(), SCHEMA_VERSION);
+    assert_eq!(contract_client.migrate(), SCHEMA_VERSION);
+
+    // A test contract runs the empty Wasm, so upgrading to it succeeds, but
+    // only with the admin's authorization
+    let wasm_hash: BytesN<32> = env.crypto().sha256(&Bytes::new(&env)).into();
+    env.set_auths(&[]);
+    assert!(contract_client.try_upgrade(&wasm_hash).is_err());
+    env.mock_all_auths();
+    contract_client.upgrade(&wasm_hash);
+    assert_eq!(env.auths()[0].0, admin);
+
+    // Only code that was uploaded can be installed
+    let unknown_hash = BytesN::from_array(&env, &[1u8; 32]);
+    assert!(contract_client.try_upgrade(&unknown_hash).is_err());
+}
+
+#[test]
+#[should_panic(expected = "Stored data has a newer schema version")]
+fn test_migrate_newer_schema() {
+    let env = Env::default();
+    let contract_address = env.register(CertifyingAuthority, ());
+    let contract_client = CertifyingAuthorityClient::new(&env, &contract_address);
+
+    env.mock_all_auths();
+    contract_client.initialize(&Address::generate(&env));
+
+    // Code older than the stored data refuses to migrate it
+    env.as_contract(&contract_address, || {
+        env.storage()
+            .instance()
+            .set(&DataKey::SchemaVersion, &(SCHEMA_VERSION + 1));
+    });
+    contract_client.migrate();
+}
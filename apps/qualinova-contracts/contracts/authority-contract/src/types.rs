@@ -6,6 +6,7 @@ pub enum DataKey {
     AuthorityIds,         // List of all authority IDs (public keys)
     NextAuthorityId,      // Counter for generating unique authority IDs
     Admin,
+    SchemaVersion, // Version of the storage layout
 }
 
 #[contracttype]
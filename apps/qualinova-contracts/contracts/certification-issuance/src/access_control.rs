@@ -1,22 +1,25 @@
-use crate::{Certificate, CertificateId};
-use alloc::format;
-use soroban_sdk::{Address, Bytes, Env, Symbol, Vec};
+use crate::{AdminProposal, Certificate, Error};
+use soroban_sdk::{Address, Env, Symbol, Vec};
 
 // Storage keys
 const ADMIN_KEY: &str = "admin";
 const ISSUERS_KEY: &str = "issuers";
+const ADMIN_PROPOSAL_KEY: &str = "admin_proposal";
 
 // Events
 const ISSUER_ADDED_EVENT: &str = "issuer_added";
 const ISSUER_REMOVED_EVENT: &str = "issuer_removed";
+const ADMIN_PROPOSED_EVENT: &str = "admin_proposed";
+const ADMIN_PROPOSAL_CANCELLED_EVENT: &str = "admin_proposal_cancelled";
 const ADMIN_TRANSFERRED_EVENT: &str = "admin_transferred";
-const CERTIFICATE_REVOKED_EVENT: &str = "certificate_revoked";
 
 // Initialize the contract with an admin
-pub fn initialize(env: &Env, admin: &Address) {
+pub fn initialize(env: &Env, admin: &Address) -> Result<(), Error> {
+    admin.require_auth();
+    
     // Check if already initialized
     if is_initialized(env) {
-        panic!("Contract already initialized");
+        return Err(Error::AlreadyInitialized);
     }
     
     // Set the admin
@@ -25,6 +28,8 @@ pub fn initialize(env: &Env, admin: &Address) {
     // Initialize empty issuers list
     let issuers: Vec<Address> = Vec::new(env);
     env.storage().instance().set(&Symbol::new(env, ISSUERS_KEY), &issuers);
+    
+    Ok(())
 }
 
 // Check if the contract is initialized
@@ -32,42 +37,117 @@ pub fn is_initialized(env: &Env) -> bool {
     env.storage().instance().has(&Symbol::new(env, ADMIN_KEY))
 }
 
-// Check if the contract has an admin
-fn has_admin(env: &Env) -> bool {
-    env.storage().instance().has(&Symbol::new(env, ADMIN_KEY))
-}
-
 // Get the admin address
-pub fn get_admin(env: &Env) -> Address {
+pub fn get_admin(env: &Env) -> Result<Address, Error> {
     env.storage()
         .instance()
         .get(&Symbol::new(env, ADMIN_KEY))
-        .unwrap_or_else(|| panic!("Contract not initialized"))
+        .ok_or(Error::NotInitialized)
 }
 
-// Transfer admin role to a new address
-pub fn transfer_admin(env: &Env, new_admin: Address) -> bool {
-    // Verify the caller is the current admin
-    let admin = get_admin(env);
+// Require the admin's authorization and return the admin address
+pub fn require_admin(env: &Env) -> Result<Address, Error> {
+    let admin = get_admin(env)?;
     admin.require_auth();
+    Ok(admin)
+}
+
+// Propose a new admin. A new proposal replaces any pending one.
+pub fn propose_admin(
+    env: &Env,
+    new_admin: Address,
+    expires_at: Option<u64>,
+) -> Result<bool, Error> {
+    // Verify the caller is the current admin
+    let admin = require_admin(env)?;
+    
+    // Check the proposed admin and the expiry
+    if new_admin == admin {
+        return Err(Error::InvalidInput);
+    }
+    let proposed_at = env.ledger().timestamp();
+    if expires_at.is_some_and(|expires_at| expires_at <= proposed_at) {
+        return Err(Error::InvalidExpirationDate);
+    }
     
-    // Set the new admin
-    env.storage().instance().set(&Symbol::new(env, ADMIN_KEY), &new_admin);
+    // Store the proposal
+    env.storage().instance().set(
+        &Symbol::new(env, ADMIN_PROPOSAL_KEY),
+        &AdminProposal {
+            proposed_admin: new_admin.clone(),
+            proposed_at,
+            expires_at,
+        },
+    );
+    
+    // Emit admin proposed event
+    env.events().publish(
+        (Symbol::new(env, ADMIN_PROPOSED_EVENT),),
+        (admin, new_admin, expires_at),
+    );
+    
+    Ok(true)
+}
+
+// Accept the pending proposal and take over the admin role
+pub fn accept_admin(env: &Env) -> Result<bool, Error> {
+    let admin = get_admin(env)?;
+    let proposal = get_admin_proposal(env)?;
+    
+    // Require authorization from the proposed admin
+    proposal.proposed_admin.require_auth();
+    
+    if proposal
+        .expires_at
+        .is_some_and(|expires_at| env.ledger().timestamp() >= expires_at)
+    {
+        return Err(Error::AdminProposalExpired);
+    }
+    
+    // Hand over the admin role
+    env.storage().instance().remove(&Symbol::new(env, ADMIN_PROPOSAL_KEY));
+    env.storage()
+        .instance()
+        .set(&Symbol::new(env, ADMIN_KEY), &proposal.proposed_admin);
     
     // Emit admin transferred event
     env.events().publish(
         (Symbol::new(env, ADMIN_TRANSFERRED_EVENT),),
-        (admin, new_admin),
+        (admin, proposal.proposed_admin),
     );
     
-    true
+    Ok(true)
+}
+
+// Withdraw the pending admin proposal
+pub fn cancel_admin_proposal(env: &Env) -> Result<bool, Error> {
+    // Verify the caller is the current admin
+    let admin = require_admin(env)?;
+    
+    let proposal = get_admin_proposal(env)?;
+    env.storage().instance().remove(&Symbol::new(env, ADMIN_PROPOSAL_KEY));
+    
+    // Emit admin proposal cancelled event
+    env.events().publish(
+        (Symbol::new(env, ADMIN_PROPOSAL_CANCELLED_EVENT),),
+        (admin, proposal.proposed_admin),
+    );
+    
+    Ok(true)
+}
+
+// Get the pending admin proposal, expired or not
+pub fn get_admin_proposal(env: &Env) -> Result<AdminProposal, Error> {
+    env.storage()
+        .instance()
+        .get(&Symbol::new(env, ADMIN_PROPOSAL_KEY))
+        .ok_or(Error::AdminProposalNotFound)
 }
 
 // Add an issuer
-pub fn add_issuer(env: &Env, issuer: Address) -> bool {
+pub fn add_issuer(env: &Env, issuer: Address) -> Result<bool, Error> {
     // Verify the caller is the admin
-    let admin = get_admin(env);
-    admin.require_auth();
+    let admin = require_admin(env)?;
     
     // Get the current issuers
     let mut issuers: Vec<Address> = env
@@ -79,7 +159,7 @@ pub fn add_issuer(env: &Env, issuer: Address) -> bool {
     // Check if the issuer is already added
     for i in 0..issuers.len() {
         if issuers.get(i).unwrap() == issuer {
-            return false; // Already an issuer
+            return Ok(false); // Already an issuer
         }
     }
     
@@ -93,14 +173,13 @@ pub fn add_issuer(env: &Env, issuer: Address) -> bool {
         (admin, issuer),
     );
     
-    true
+    Ok(true)
 }
 
 // Remove an issuer
-pub fn remove_issuer(env: &Env, issuer: Address) -> bool {
+pub fn remove_issuer(env: &Env, issuer: Address) -> Result<bool, Error> {
     // Verify the caller is the admin
-    let admin = get_admin(env);
-    admin.require_auth();
+    let admin = require_admin(env)?;
     
     // Get the current issuers
     let issuers: Vec<Address> = env
@@ -124,7 +203,7 @@ pub fn remove_issuer(env: &Env, issuer: Address) -> bool {
     
     // If issuer was not found, return false
     if !found {
-        return false;
+        return Ok(false);
     }
     
     // Update the issuers list
@@ -136,13 +215,13 @@ pub fn remove_issuer(env: &Env, issuer: Address) -> bool {
         (admin, issuer),
     );
     
-    true
+    Ok(true)
 }
 
 // Check if an address is an issuer
 pub fn is_issuer(env: &Env, address: Address) -> bool {
     // Admin is always considered an issuer
-    if has_admin(env) && get_admin(env) == address {
+    if get_admin(env).is_ok_and(|admin| admin == address) {
         return true;
     }
     
@@ -169,57 +248,28 @@ pub fn get_issuers(env: &Env) -> Vec<Address> {
         .unwrap_or_else(|| Vec::new(env))
 }
 
-// Require the caller to be an issuer or admin
-pub fn require_issuer(env: &Env) -> Address {
-    // Get the transaction source account
-    // In Soroban, we'll use the current contract's ID as a fallback
-    // since we don't have direct access to the transaction source
-    let _contract_id = env.current_contract_address();
+// Require the given address to authorize the call and be an issuer or admin
+pub fn require_issuer(env: &Env, issuer: &Address) -> Result<(), Error> {
+    issuer.require_auth();
     
-    // Check if the admin is set and use that
-    let admin = get_admin(env);
-    admin.require_auth();
+    if !is_issuer(env, issuer.clone()) {
+        return Err(Error::Unauthorized);
+    }
     
-    // Return the admin as the authorized issuer
-    admin
+    Ok(())
 }
 
-// Revoke a certificate
-pub fn revoke_certificate(env: &Env, certificate_id: CertificateId) -> bool {
-    // Verify the caller is an authorized issuer or admin
-    let caller = require_issuer(env);
-    
-    // Get the certificate
-    let cert_id_bytes = env.crypto().sha256(&Bytes::from_slice(env, &certificate_id.0.to_array()));
-    let certificate_key = get_certificate_key(env, &cert_id_bytes);
-    
-    let mut certificate: Certificate = env
-        .storage()
-        .persistent()
-        .get(&certificate_key)
-        .unwrap_or_else(|| panic!("Certificate not found"));
+// Require the given issuer to have issued the certificate, or to be the admin
+pub fn require_certificate_issuer(
+    env: &Env,
+    issuer: &Address,
+    certificate: &Certificate,
+) -> Result<(), Error> {
+    require_issuer(env, issuer)?;
     
-    // Check if already revoked
-    if certificate.revoked {
-        return false;
+    if certificate.issuer != *issuer && get_admin(env)? != *issuer {
+        return Err(Error::NotCertificateIssuer);
     }
     
-    // Update the certificate
-    certificate.revoked = true;
-    env.storage().persistent().set(&certificate_key, &certificate);
-    
-    // Emit certificate revoked event
-    env.events().publish(
-        (Symbol::new(env, CERTIFICATE_REVOKED_EVENT),),
-        (certificate_id, certificate.issuer, caller),
-    );
-    
-    true
-}
-
-// Helper function to get a certificate key from a hash
-fn get_certificate_key(env: &Env, hash: &soroban_sdk::crypto::Hash<32>) -> Symbol {
-    // Create a simple key with the hash - avoid using colons
-    let key = format!("cert{:x}", hash.to_array()[0]);
-    Symbol::new(env, &key)
+    Ok(())
 }
@@ -0,0 +1,212 @@
+use crate::{
+    access_control, index, issuance, lifecycle, storage, verification, Certificate,
+    CertificateId, CertificateMetadata, CertificateStatus, CertificateVersion, Error,
+};
+use soroban_sdk::{Address, BytesN, Env, String, Symbol, Vec};
+
+// Events
+const CERTIFICATE_AMENDED_EVENT: &str = "certificate_amended";
+
+// Amend a certificate's metadata, for example to extend or correct its scope.
+//
+// The issuer signs the new content and the root it committed over its fields
+// together with the certificate ID and the new version number, so a signature
+// can never be replayed onto another certificate or used to roll a certificate
+// back to an earlier version.
+pub fn amend_certificate(
+    env: &Env,
+    issuer: Address,
+    certificate_id: CertificateId,
+    metadata: CertificateMetadata,
+    metadata_root: BytesN<32>,
+    note: String,
+    signing_key: BytesN<32>,
+    signature: BytesN<64>,
+) -> Result<u32, Error> {
+    // Get the certificate
+    let mut certificate = storage::get_certificate(env, &certificate_id)
+        .ok_or(Error::CertificateNotFound)?;
+    
+    // Only the issuing issuer or the admin may amend
+    access_control::require_certificate_issuer(env, &issuer, &certificate)?;
+    
+    // Only current certificates can be amended
+    match lifecycle::effective_status(env, &certificate) {
+        CertificateStatus::Active | CertificateStatus::Suspended => {}
+        CertificateStatus::Withdrawn => return Err(Error::CertificateWithdrawn),
+        CertificateStatus::Expired => return Err(Error::CertificateExpired),
+        CertificateStatus::Superseded => return Err(Error::CertificateSuperseded),
+    }
+    
+    // Keep the issued content as version 1 before it is replaced
+    record_initial_version(env, &certificate);
+    
+    // Create the new version and check the issuer's signature over it
+    let version = storage::get_version_count(env, &certificate_id) + 1;
+    let signing_key = issuance::check_signing_key(env, &issuer, signing_key)?;
+    let content_hash = version_hash(
+        env,
+        &certificate,
+        &certificate.owner,
+        &metadata,
+        Some(&metadata_root),
+        certificate.expiration_date,
+        version,
+    );
+    let new_version = CertificateVersion {
+        version,
+        owner: certificate.owner.clone(),
+        metadata: metadata.clone(),
+        expiration_date: certificate.expiration_date,
+        content_hash,
+        signature: signature.clone(),
+        signing_key: signing_key.clone(),
+        recorded_at: env.ledger().timestamp(),
+        recorded_by: issuer.clone(),
+        note,
+    };
+    verification::check_version_signature(env, &new_version)?;
+    
+    // Record the new version
+    storage::add_certificate_version(env, &certificate_id, &new_version);
+    
+    // Update the certificate to the new version and commit to its metadata
+    index::change_achievement_type(env, &certificate, &metadata.achievement_type);
+    storage::set_metadata_root(env, &certificate_id, &metadata_root);
+    certificate.metadata = metadata;
+    certificate.signature = signature;
+    certificate.signing_key = signing_key;
+    storage::set_certificate(env, &certificate);
+    
+    // Emit certificate amended event
+    env.events().publish(
+        (Symbol::new(env, CERTIFICATE_AMENDED_EVENT),),
+        (certificate_id, version, issuer),
+    );
+    
+    Ok(version)
+}
+
+// Get a version of a certificate
+pub fn get_certificate_version(
+    env: &Env,
+    certificate_id: CertificateId,
+    version: u32,
+) -> Result<CertificateVersion, Error> {
+    let certificate = storage::get_certificate(env, &certificate_id)
+        .ok_or(Error::CertificateNotFound)?;
+    
+    // Certificates that were never amended or transferred only have the issued content
+    if version == 1 && storage::get_version_count(env, &certificate_id) == 0 {
+        return Ok(initial_version(env, &certificate));
+    }
+    
+    storage::get_certificate_version(env, &certificate_id, version).ok_or(Error::VersionNotFound)
+}
+
+// Get every version of a certificate, oldest first
+pub fn get_certificate_history(
+    env: &Env,
+    certificate_id: CertificateId,
+) -> Result<Vec<CertificateVersion>, Error> {
+    let certificate = storage::get_certificate(env, &certificate_id)
+        .ok_or(Error::CertificateNotFound)?;
+    
+    let count = storage::get_version_count(env, &certificate_id);
+    if count == 0 {
+        return Ok(Vec::from_array(env, [initial_version(env, &certificate)]));
+    }
+    
+    let mut history = Vec::new(env);
+    for version in 1..=count {
+        history.push_back(storage::get_certificate_version(env, &certificate_id, version).unwrap());
+    }
+    
+    Ok(history)
+}
+
+// Get the version a certificate's current signature covers
+pub fn current_version(env: &Env, certificate: &Certificate) -> CertificateVersion {
+    let count = storage::get_version_count(env, &certificate.id);
+    if count == 0 {
+        return initial_version(env, certificate);
+    }
+    
+    storage::get_certificate_version(env, &certificate.id, count).unwrap()
+}
+
+// Record the issued content as version 1, if no version is recorded yet.
+// This runs before anything that changes signed fields, such as an amendment
+// or a transfer to a new owner.
+pub fn record_initial_version(env: &Env, certificate: &Certificate) {
+    if storage::get_version_count(env, &certificate.id) == 0 {
+        storage::add_certificate_version(env, &certificate.id, &initial_version(env, certificate));
+    }
+}
+
+// Build version 1 from a certificate that was never amended or transferred
+fn initial_version(env: &Env, certificate: &Certificate) -> CertificateVersion {
+    let metadata_root = storage::get_metadata_root(env, &certificate.id);
+    issued_version(env, certificate, metadata_root.as_ref(), certificate.expiration_date)
+}
+
+// Build version 1 of a certificate as the issuer signed it, with the expiration
+// date that was submitted rather than the one the type's validity period set
+pub fn issued_version(
+    env: &Env,
+    certificate: &Certificate,
+    metadata_root: Option<&BytesN<32>>,
+    signed_expiration: Option<u64>,
+) -> CertificateVersion {
+    CertificateVersion {
+        version: 1,
+        owner: certificate.owner.clone(),
+        metadata: certificate.metadata.clone(),
+        expiration_date: signed_expiration,
+        content_hash: version_hash(
+            env,
+            certificate,
+            &certificate.owner,
+            &certificate.metadata,
+            metadata_root,
+            signed_expiration,
+            1,
+        ),
+        signature: certificate.signature.clone(),
+        signing_key: certificate.signing_key.clone(),
+        recorded_at: certificate.issuance_date,
+        recorded_by: certificate.issuer.clone(),
+        note: String::from_str(env, ""),
+    }
+}
+
+// Get the hash the issuer signed for a version of a certificate
+fn version_hash(
+    env: &Env,
+    certificate: &Certificate,
+    owner: &Address,
+    metadata: &CertificateMetadata,
+    metadata_root: Option<&BytesN<32>>,
+    expiration_date: Option<u64>,
+    version: u32,
+) -> BytesN<32> {
+    let predecessor = storage::get_predecessor(env, &certificate.id);
+    let message = verification::create_verification_message(
+        env,
+        owner,
+        &certificate.issuer,
+        &certificate.cert_type_id,
+        metadata,
+        metadata_root,
+        expiration_date,
+        predecessor.as_ref(),
+    );
+    
+    let message = if version > 1 {
+        verification::create_amendment_message(env, &message, &certificate.id, version)
+    } else {
+        message
+    };
+    
+    message.try_into().unwrap()
+}
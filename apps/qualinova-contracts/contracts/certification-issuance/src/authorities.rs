@@ -0,0 +1,84 @@
+use crate::{access_control, Error};
+use soroban_sdk::{contractclient, contracttype, Address, Env, String, Symbol, Vec};
+
+// Instance storage key of the authority registry address
+const AUTHORITY_REGISTRY_KEY: &str = "authority_registry";
+
+// Events
+const AUTHORITY_REGISTRY_SET_EVENT: &str = "authority_registry_set";
+
+// Status of a certifying authority, mirrored from the authority contract
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AuthorityStatus {
+    Active,
+    Inactive,
+}
+
+// Certifying authority, mirrored from the authority contract
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Authority {
+    pub authority_id: String,
+    pub name: String,
+    pub public_key: Address,
+    pub registration_date: u64,
+    pub accreditation_info: String,
+    pub allowed_cert_types: Vec<String>,
+    pub status: AuthorityStatus,
+}
+
+// The part of the authority contract interface this contract calls
+#[allow(dead_code)]
+#[contractclient(name = "AuthorityRegistryClient")]
+pub trait AuthorityRegistry {
+    fn get_authority(env: Env, authority_id: Address) -> Authority;
+}
+
+// Set the authority contract that issuers' accreditation is checked against
+pub fn set_authority_registry(env: &Env, registry: Address) -> Result<bool, Error> {
+    // Verify the caller is the admin
+    let admin = access_control::require_admin(env)?;
+    
+    env.storage()
+        .instance()
+        .set(&Symbol::new(env, AUTHORITY_REGISTRY_KEY), &registry);
+    
+    // Emit registry set event
+    env.events().publish(
+        (Symbol::new(env, AUTHORITY_REGISTRY_SET_EVENT),),
+        (admin, registry),
+    );
+    
+    Ok(true)
+}
+
+// Get the authority contract address
+pub fn get_authority_registry(env: &Env) -> Result<Address, Error> {
+    env.storage()
+        .instance()
+        .get(&Symbol::new(env, AUTHORITY_REGISTRY_KEY))
+        .ok_or(Error::AuthorityRegistryNotSet)
+}
+
+// Require the issuer to be an active authority accredited for the certification type.
+// Authorities are keyed by their public key, which is the issuer's address.
+pub fn require_accredited(env: &Env, issuer: &Address, cert_type_id: &String) -> Result<(), Error> {
+    let registry = get_authority_registry(env)?;
+    let client = AuthorityRegistryClient::new(env, &registry);
+    
+    let authority = match client.try_get_authority(issuer) {
+        Ok(Ok(authority)) => authority,
+        _ => return Err(Error::AuthorityNotFound),
+    };
+    
+    if authority.status != AuthorityStatus::Active {
+        return Err(Error::AuthorityInactive);
+    }
+    
+    if !authority.allowed_cert_types.contains(cert_type_id) {
+        return Err(Error::NotAccreditedForType);
+    }
+    
+    Ok(())
+}
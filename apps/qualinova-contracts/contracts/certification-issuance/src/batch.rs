@@ -0,0 +1,113 @@
+use crate::{
+    lifecycle, revocation, storage, transfer, BatchItemResult, BatchMode, CertificateId,
+    CertificateStatus, Error, RevocationReason,
+};
+use soroban_sdk::{Address, Env, String, Vec};
+
+// Most items a batch may hold, so a batch issuance stays within the instruction
+// limit of a transaction
+pub const MAX_BATCH_SIZE: u32 = 20;
+
+// Check that a batch holds no more than the maximum number of items
+pub fn check_batch_size(count: u32) -> Result<(), Error> {
+    if count > MAX_BATCH_SIZE {
+        return Err(Error::BatchTooLarge);
+    }
+    
+    Ok(())
+}
+
+// Add the result of an item to a batch. In all-or-nothing mode a failure is
+// returned instead, which aborts the batch and rolls back the items before it.
+pub fn record_result(
+    results: &mut Vec<BatchItemResult>,
+    result: Result<CertificateId, Error>,
+    mode: BatchMode,
+) -> Result<(), Error> {
+    match result {
+        Ok(certificate_id) => results.push_back(BatchItemResult::Success(certificate_id)),
+        Err(error) if mode == BatchMode::BestEffort => {
+            results.push_back(BatchItemResult::Failure(error as u32))
+        }
+        Err(error) => return Err(error),
+    }
+    
+    Ok(())
+}
+
+// Revoke several certificates with the same reason, note and effective date.
+// Certificates that are already withdrawn count as revoked.
+pub fn batch_revoke_certificates(
+    env: &Env,
+    issuer: Address,
+    certificate_ids: Vec<CertificateId>,
+    reason: RevocationReason,
+    note: String,
+    effective_date: Option<u64>,
+    mode: BatchMode,
+) -> Result<Vec<BatchItemResult>, Error> {
+    check_batch_size(certificate_ids.len())?;
+    
+    let mut results = Vec::new(env);
+    
+    for certificate_id in certificate_ids.iter() {
+        let result = revocation::revoke_certificate(
+            env,
+            issuer.clone(),
+            certificate_id.clone(),
+            reason,
+            note.clone(),
+            effective_date,
+        )
+        .map(|_| certificate_id);
+        
+        record_result(&mut results, result, mode)?;
+    }
+    
+    Ok(results)
+}
+
+// Transfer several certificates, each to its own new owner. Certificates that
+// are not active fail with the error matching their status, and transfers that
+// a party did not authorize fail with `Unauthorized`.
+pub fn batch_transfer_certificates(
+    env: &Env,
+    certificate_ids: Vec<CertificateId>,
+    new_owners: Vec<Address>,
+    mode: BatchMode,
+) -> Result<Vec<BatchItemResult>, Error> {
+    // Validate input arrays have the same length
+    if new_owners.len() != certificate_ids.len() {
+        return Err(Error::InputLengthMismatch);
+    }
+    check_batch_size(certificate_ids.len())?;
+    
+    let mut results = Vec::new(env);
+    
+    for (certificate_id, new_owner) in certificate_ids.iter().zip(new_owners.iter()) {
+        let result = transfer::transfer_batch_item(env, certificate_id.clone(), new_owner)
+            .and_then(|transferred| {
+                if transferred {
+                    Ok(certificate_id.clone())
+                } else {
+                    Err(inactive_error(env, &certificate_id))
+                }
+            });
+        
+        record_result(&mut results, result, mode)?;
+    }
+    
+    Ok(results)
+}
+
+// Get the error matching the status of a certificate that is not active
+fn inactive_error(env: &Env, certificate_id: &CertificateId) -> Error {
+    let certificate = storage::get_certificate(env, certificate_id).unwrap();
+    
+    match lifecycle::effective_status(env, &certificate) {
+        CertificateStatus::Withdrawn => Error::CertificateWithdrawn,
+        CertificateStatus::Expired => Error::CertificateExpired,
+        CertificateStatus::Superseded => Error::CertificateSuperseded,
+        CertificateStatus::Suspended | CertificateStatus::Active => Error::CertificateSuspended,
+    }
+}
@@ -0,0 +1,145 @@
+use crate::{access_control, Error};
+use soroban_sdk::{contractclient, contracttype, Address, Env, String, Symbol, Vec};
+
+// Instance storage key of the certification type registry address
+const CERT_TYPE_REGISTRY_KEY: &str = "cert_type_registry";
+
+// Events
+const CERT_TYPE_REGISTRY_SET_EVENT: &str = "cert_type_registry_set";
+
+// Seconds per unit of a validity period
+const SECONDS_PER_DAY: u64 = 86_400;
+const SECONDS_PER_WEEK: u64 = 7 * SECONDS_PER_DAY;
+const SECONDS_PER_MONTH: u64 = 30 * SECONDS_PER_DAY;
+const SECONDS_PER_YEAR: u64 = 365 * SECONDS_PER_DAY;
+
+// Longest validity period string that is parsed, e.g. "3 years"
+const MAX_VALIDITY_PERIOD_LEN: usize = 32;
+
+// Status of a certification type, mirrored from the certification-type-management contract
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CertTypeStatus {
+    Active,
+    Deprecated,
+}
+
+// Certification type, mirrored from the certification-type-management contract
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CertificationType {
+    pub cert_type_id: String,
+    pub name: String,
+    pub version: String,
+    pub description: String,
+    pub industry_scope: Vec<String>,
+    pub validity_period: String,
+    pub required_evidence: Vec<String>,
+    pub verification_requirements: String,
+    pub status: CertTypeStatus,
+    pub authorities: Vec<String>,
+    pub deprecation_reason: Option<String>,
+}
+
+// The part of the certification-type-management interface this contract calls
+#[allow(dead_code)]
+#[contractclient(name = "CertificationTypeRegistryClient")]
+pub trait CertificationTypeRegistry {
+    fn get_certification_type_details(env: Env, cert_type_id: String) -> CertificationType;
+}
+
+// Set the certification-type-management contract that issuance checks against
+pub fn set_certification_type_registry(env: &Env, registry: Address) -> Result<bool, Error> {
+    // Verify the caller is the admin
+    let admin = access_control::require_admin(env)?;
+    
+    env.storage()
+        .instance()
+        .set(&Symbol::new(env, CERT_TYPE_REGISTRY_KEY), &registry);
+    
+    // Emit registry set event
+    env.events().publish(
+        (Symbol::new(env, CERT_TYPE_REGISTRY_SET_EVENT),),
+        (admin, registry),
+    );
+    
+    Ok(true)
+}
+
+// Get the certification-type-management contract address
+pub fn get_certification_type_registry(env: &Env) -> Result<Address, Error> {
+    env.storage()
+        .instance()
+        .get(&Symbol::new(env, CERT_TYPE_REGISTRY_KEY))
+        .ok_or(Error::TypeRegistryNotSet)
+}
+
+// Look up a certification type and require it to be active
+pub fn get_active_certification_type(
+    env: &Env,
+    cert_type_id: &String,
+) -> Result<CertificationType, Error> {
+    let registry = get_certification_type_registry(env)?;
+    let client = CertificationTypeRegistryClient::new(env, &registry);
+    
+    let cert_type = match client.try_get_certification_type_details(cert_type_id) {
+        Ok(Ok(cert_type)) => cert_type,
+        _ => return Err(Error::CertificationTypeNotFound),
+    };
+    
+    if cert_type.status != CertTypeStatus::Active {
+        return Err(Error::CertificationTypeInactive);
+    }
+    
+    Ok(cert_type)
+}
+
+// Get the default expiration of a certificate of this type issued at `issuance_date`.
+// Types with an empty validity period issue certificates that do not expire.
+pub fn default_expiration(
+    cert_type: &CertificationType,
+    issuance_date: u64,
+) -> Result<Option<u64>, Error> {
+    if cert_type.validity_period.is_empty() {
+        return Ok(None);
+    }
+    
+    let period = parse_validity_period(&cert_type.validity_period)?;
+    issuance_date
+        .checked_add(period)
+        .map(Some)
+        .ok_or(Error::InvalidValidityPeriod)
+}
+
+// Parse a validity period such as "3 years", "18 months", "2 weeks" or "90 days"
+// into seconds. Months count as 30 days and years as 365 days.
+fn parse_validity_period(validity_period: &String) -> Result<u64, Error> {
+    let len = validity_period.len() as usize;
+    if len > MAX_VALIDITY_PERIOD_LEN {
+        return Err(Error::InvalidValidityPeriod);
+    }
+    
+    let mut buffer = [0u8; MAX_VALIDITY_PERIOD_LEN];
+    validity_period.copy_into_slice(&mut buffer[..len]);
+    let text = core::str::from_utf8(&buffer[..len]).map_err(|_| Error::InvalidValidityPeriod)?;
+    
+    let mut parts = text.split_whitespace();
+    let (Some(amount), Some(unit), None) = (parts.next(), parts.next(), parts.next()) else {
+        return Err(Error::InvalidValidityPeriod);
+    };
+    
+    let amount: u64 = amount.parse().map_err(|_| Error::InvalidValidityPeriod)?;
+    let unit = unit.strip_suffix(['s', 'S']).unwrap_or(unit);
+    let unit_seconds = [
+        ("day", SECONDS_PER_DAY),
+        ("week", SECONDS_PER_WEEK),
+        ("month", SECONDS_PER_MONTH),
+        ("year", SECONDS_PER_YEAR),
+    ]
+    .iter()
+    .find(|(name, _)| unit.eq_ignore_ascii_case(name))
+    .map(|(_, seconds)| *seconds)
+    .ok_or(Error::InvalidValidityPeriod)?;
+    
+    amount.checked_mul(unit_seconds).ok_or(Error::InvalidValidityPeriod)
+}
@@ -0,0 +1,70 @@
+use crate::{storage, CertificateId, DisclosedField, Error};
+use soroban_sdk::xdr::ToXdr;
+use soroban_sdk::{Bytes, BytesN, Env, Vec};
+
+// Domain separation between leaves and inner nodes, so a node can never be
+// presented as a field
+const LEAF_PREFIX: u8 = 0;
+const NODE_PREFIX: u8 = 1;
+
+// Get the Merkle root the issuer committed over a certificate's metadata fields.
+// Certificates migrated from the legacy layout have none.
+pub fn get_metadata_root(env: &Env, certificate_id: CertificateId) -> Result<BytesN<32>, Error> {
+    if !storage::has_certificate(env, &certificate_id) {
+        return Err(Error::CertificateNotFound);
+    }
+    
+    storage::get_metadata_root(env, &certificate_id).ok_or(Error::MetadataRootNotFound)
+}
+
+// Check disclosed metadata fields against a certificate's committed root. Each
+// field comes with the salt and the proof at the same position in `salts` and
+// `proofs`.
+pub fn verify_disclosed_fields(
+    env: &Env,
+    certificate_id: CertificateId,
+    fields: Vec<DisclosedField>,
+    salts: Vec<BytesN<32>>,
+    proofs: Vec<Vec<BytesN<32>>>,
+) -> Result<bool, Error> {
+    if fields.is_empty() {
+        return Err(Error::InvalidInput);
+    }
+    if fields.len() != salts.len() || fields.len() != proofs.len() {
+        return Err(Error::InputLengthMismatch);
+    }
+    
+    let root = get_metadata_root(env, certificate_id)?;
+    
+    Ok((0..fields.len()).all(|i| {
+        let leaf = leaf_hash(env, &salts.get(i).unwrap(), &fields.get(i).unwrap());
+        let computed = proofs
+            .get(i)
+            .unwrap()
+            .iter()
+            .fold(leaf, |node, sibling| node_hash(env, &node, &sibling));
+        computed == root
+    }))
+}
+
+// Hash a field as a leaf. The salt is random per field and only known to the
+// issuer and the holder, so a leaf cannot be matched against guessed values.
+pub fn leaf_hash(env: &Env, salt: &BytesN<32>, field: &DisclosedField) -> BytesN<32> {
+    let mut data = Bytes::from_array(env, &[LEAF_PREFIX]);
+    data.append(&Bytes::from_array(env, &salt.to_array()));
+    data.append(&field.clone().to_xdr(env));
+    
+    env.crypto().sha256(&data).into()
+}
+
+// Hash two nodes into their parent. The pair is sorted first, so proofs do not
+// need to say which side each sibling is on.
+pub fn node_hash(env: &Env, a: &BytesN<32>, b: &BytesN<32>) -> BytesN<32> {
+    let (left, right) = if a < b { (a, b) } else { (b, a) };
+    
+    let mut data = Bytes::from_array(env, &[NODE_PREFIX]);
+    data.append(&Bytes::from_array(env, &left.to_array()));
+    data.append(&Bytes::from_array(env, &right.to_array()));
+    
+    env.crypto().sha256(&data).into()
+}
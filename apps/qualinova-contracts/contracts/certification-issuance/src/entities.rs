@@ -0,0 +1,116 @@
+use crate::{access_control, CertificateId, Error};
+use soroban_sdk::{contractclient, contracttype, Address, Env, String, Symbol, Vec};
+
+// Instance storage key of the entity registry address
+const ENTITY_REGISTRY_KEY: &str = "entity_registry";
+
+// Events
+const ENTITY_REGISTRY_SET_EVENT: &str = "entity_registry_set";
+
+// Status of a certifiable entity, mirrored from the entity contract
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum EntityStatus {
+    Active,
+    Inactive,
+}
+
+// Certifiable entity, mirrored from the entity contract
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Entity {
+    pub entity_id: String,
+    pub name: String,
+    pub public_key: Address,
+    pub registration_date: u64,
+    pub industry_sector: String,
+    pub location: String,
+    pub contact_info: String,
+    pub status: EntityStatus,
+    pub certifications: Vec<String>,
+}
+
+// The part of the entity contract interface this contract calls
+#[allow(dead_code)]
+#[contractclient(name = "EntityRegistryClient")]
+pub trait EntityRegistry {
+    fn get_entity(env: Env, entity_id: Address) -> Entity;
+    fn add_certification(env: Env, entity_id: Address, certification_id: String);
+}
+
+// Set the entity contract that certificates issued to registered entities are recorded in
+pub fn set_entity_registry(env: &Env, registry: Address) -> Result<bool, Error> {
+    // Verify the caller is the admin
+    let admin = access_control::require_admin(env)?;
+    
+    env.storage()
+        .instance()
+        .set(&Symbol::new(env, ENTITY_REGISTRY_KEY), &registry);
+    
+    // Emit registry set event
+    env.events().publish(
+        (Symbol::new(env, ENTITY_REGISTRY_SET_EVENT),),
+        (admin, registry),
+    );
+    
+    Ok(true)
+}
+
+// Get the entity contract address
+pub fn get_entity_registry(env: &Env) -> Result<Address, Error> {
+    env.storage()
+        .instance()
+        .get(&Symbol::new(env, ENTITY_REGISTRY_KEY))
+        .ok_or(Error::EntityRegistryNotSet)
+}
+
+// Get the entity registry a certificate for `owner` must be recorded in, if the
+// owner is a registered entity. Owners that are not entities, and deployments
+// without an entity registry, are left alone; registered entities must be active
+// to receive certificates.
+pub fn owner_entity_registry(env: &Env, owner: &Address) -> Result<Option<Address>, Error> {
+    let Ok(registry) = get_entity_registry(env) else {
+        return Ok(None);
+    };
+    let client = EntityRegistryClient::new(env, &registry);
+    
+    let entity = match client.try_get_entity(owner) {
+        Ok(Ok(entity)) => entity,
+        _ => return Ok(None),
+    };
+    
+    if entity.status != EntityStatus::Active {
+        return Err(Error::EntityInactive);
+    }
+    
+    Ok(Some(registry))
+}
+
+// Record a certificate with its owner's entity. The entity contract only accepts
+// records from the certification contract it was pointed at, so a refusal from
+// an entity that is still active means the registry was not set up for this
+// contract.
+pub fn record_certification(
+    env: &Env,
+    registry: &Address,
+    owner: &Address,
+    certificate_id: &CertificateId,
+) -> Result<(), Error> {
+    let client = EntityRegistryClient::new(env, registry);
+    
+    match client.try_add_certification(owner, &certification_id(env, certificate_id)) {
+        Ok(Ok(())) => Ok(()),
+        _ => match client.try_get_entity(owner) {
+            Ok(Ok(entity)) if entity.status != EntityStatus::Active => Err(Error::EntityInactive),
+            _ => Err(Error::EntityRegistryNotSet),
+        },
+    }
+}
+
+// Hex-encode a certificate ID the way the entity contract lists certifications
+pub fn certification_id(env: &Env, certificate_id: &CertificateId) -> String {
+    let mut buffer = [0u8; 64];
+    hex::encode_to_slice(certificate_id.0.to_array(), &mut buffer)
+        .expect("buffer fits a hex-encoded certificate ID");
+    String::from_bytes(env, &buffer)
+}
@@ -0,0 +1,76 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    // General errors
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    Unauthorized = 3,
+    InvalidInput = 4,
+    
+    // Specific to certificates
+    CertificateNotFound = 100,
+    CertificateAlreadyExists = 101,
+    NotCertificateIssuer = 102,
+    InputLengthMismatch = 103,
+    InvalidEffectiveDate = 104,
+    RevocationNotFound = 105,
+    CertificateWithdrawn = 106,
+    CertificateExpired = 107,
+    InvalidLiftDate = 108,
+    SuspensionNotFound = 109,
+    CertificateSuspended = 110,
+    CertificateSuperseded = 111,
+    InvalidExpirationDate = 112,
+    VersionNotFound = 113,
+    NotTransferable = 114,
+    OfferNotFound = 115,
+    OfferExpired = 116,
+    NotOfferParty = 117,
+    MissingEvidence = 118,
+    EvidenceNotFound = 119,
+    MetadataRootNotFound = 120,
+    InvalidExpiryWindow = 121,
+    BatchTooLarge = 122,
+    
+    // Specific to signing keys
+    NoActiveSigningKey = 200,
+    InvalidSigningKey = 201,
+    InvalidValidityWindow = 202,
+    SigningKeyNotFound = 203,
+    BadSignature = 204,
+    
+    // Specific to certification types
+    TypeRegistryNotSet = 300,
+    CertificationTypeNotFound = 301,
+    CertificationTypeInactive = 302,
+    InvalidValidityPeriod = 303,
+    
+    // Specific to authorities
+    AuthorityRegistryNotSet = 400,
+    AuthorityNotFound = 401,
+    AuthorityInactive = 402,
+    NotAccreditedForType = 403,
+    
+    // Specific to entities
+    EntityRegistryNotSet = 500,
+    EntityInactive = 501,
+    
+    // Specific to QR verification payloads
+    InvalidPayload = 600,
+    UnsupportedPayloadVersion = 601,
+    PayloadContractMismatch = 602,
+    PayloadNetworkMismatch = 603,
+    
+    // Specific to upgrades
+    SchemaVersionTooNew = 700,
+    
+    // Specific to admin transfers
+    AdminProposalNotFound = 800,
+    AdminProposalExpired = 801,
+    
+    // Specific to the issuance guard
+    IssuanceGuardNotSet = 900,
+}
@@ -0,0 +1,47 @@
+use crate::{storage, CertificateId, CertificationType, Error, Evidence};
+use soroban_sdk::{BytesN, Env, String, Vec};
+
+// Check an evidence bundle against the evidence its certification type requires.
+// Every required item must be present, and no item may be submitted twice.
+pub fn check_evidence(
+    cert_type: &CertificationType,
+    evidence: &Vec<Evidence>,
+) -> Result<(), Error> {
+    for (i, item) in evidence.iter().enumerate() {
+        if evidence.iter().skip(i + 1).any(|other| other.name == item.name) {
+            return Err(Error::InvalidInput);
+        }
+    }
+    
+    for required in cert_type.required_evidence.iter() {
+        if !evidence.iter().any(|item| item.name == required) {
+            return Err(Error::MissingEvidence);
+        }
+    }
+    
+    Ok(())
+}
+
+// Get the evidence submitted when a certificate was issued
+pub fn get_evidence(env: &Env, certificate_id: CertificateId) -> Vec<Evidence> {
+    storage::get_evidence(env, &certificate_id)
+}
+
+// Check that a document matches the evidence submitted under a name
+pub fn verify_evidence(
+    env: &Env,
+    certificate_id: CertificateId,
+    name: String,
+    document_hash: BytesN<32>,
+) -> Result<bool, Error> {
+    if !storage::has_certificate(env, &certificate_id) {
+        return Err(Error::CertificateNotFound);
+    }
+    
+    let item = storage::get_evidence(env, &certificate_id)
+        .iter()
+        .find(|item| item.name == name)
+        .ok_or(Error::EvidenceNotFound)?;
+    
+    Ok(item.document_hash == document_hash)
+}
@@ -0,0 +1,284 @@
+use crate::storage::{ExpirySweep, IndexScope};
+use crate::{
+    access_control, lifecycle, storage, Certificate, CertificateStatus, Error,
+    ExpiringCertificatePage, ExpiryReward,
+};
+use core::cmp;
+use soroban_sdk::{token, Address, Env, Symbol, Vec};
+
+// Width of an expiry index bucket: certificates are indexed by the week they expire in
+pub const EXPIRY_BUCKET_SECONDS: u64 = 7 * 24 * 60 * 60;
+
+// Most buckets a listing may span, about six months, to bound the storage it reads
+pub const MAX_EXPIRY_BUCKETS: u64 = 26;
+
+// Instance storage key of the expiry sweep reward
+const EXPIRY_REWARD_KEY: &str = "expiry_reward";
+
+// Events
+const CERTIFICATE_EXPIRED_EVENT: &str = "certificate_expired";
+const EXPIRY_REWARD_SET_EVENT: &str = "expiry_reward_set";
+
+// Add a certificate to the expiry index of its expiration week and to its owner's
+pub fn index_expiration(env: &Env, certificate: &Certificate) {
+    let Some(expiration_date) = certificate.expiration_date else {
+        return;
+    };
+    let bucket = expiry_bucket(expiration_date);
+    let scope = IndexScope::Expiry(bucket);
+    
+    // Certificates are appended to their week, so only an earlier week can be
+    // behind the sweep. Those, such as backdated renewals, move the sweep back
+    // so they are still processed.
+    let index = storage::get_index(env, &scope).len;
+    let behind = match storage::get_expiry_sweep(env) {
+        None => true,
+        Some(sweep) => bucket < sweep.bucket,
+    };
+    if behind {
+        storage::set_expiry_sweep(env, &ExpirySweep { bucket, index });
+    }
+    
+    storage::extend_index(env, &scope, &Vec::from_array(env, [certificate.id.clone()]));
+    storage::add_to_index(
+        env,
+        &IndexScope::OwnerExpiry(certificate.owner.clone(), bucket),
+        &certificate.id,
+    );
+}
+
+// Move a certificate's expiry index entry to its new owner
+pub fn move_owner_expiration(env: &Env, certificate: &Certificate, new_owner: &Address) {
+    if let Some(expiration_date) = certificate.expiration_date {
+        let bucket = expiry_bucket(expiration_date);
+        storage::remove_from_index(
+            env,
+            &IndexScope::OwnerExpiry(certificate.owner.clone(), bucket),
+            &certificate.id,
+        );
+        storage::add_to_index(
+            env,
+            &IndexScope::OwnerExpiry(new_owner.clone(), bucket),
+            &certificate.id,
+        );
+    }
+}
+
+// List certificates expiring between `from` and `to`, inclusive, paged by cursor
+pub fn list_expiring_certificates(
+    env: &Env,
+    from: u64,
+    to: u64,
+    cursor: u32,
+    limit: u32,
+) -> Result<ExpiringCertificatePage, Error> {
+    list_expiring(env, from, to, cursor, limit, None, IndexScope::Expiry)
+}
+
+// List an owner's certificates expiring between `from` and `to`, inclusive, paged by cursor
+pub fn list_owner_expiring_certificates(
+    env: &Env,
+    owner: Address,
+    from: u64,
+    to: u64,
+    cursor: u32,
+    limit: u32,
+) -> Result<ExpiringCertificatePage, Error> {
+    list_expiring(env, from, to, cursor, limit, Some(&owner), |bucket| {
+        IndexScope::OwnerExpiry(owner.clone(), bucket)
+    })
+}
+
+// Walk the expiry index from where the last sweep stopped, marking certificates
+// past their expiration date as expired. At most `limit` index entries and weeks
+// are visited. Returns the number of certificates marked expired; the caller is
+// paid the configured reward for each.
+pub fn process_expirations(env: &Env, caller: Address, limit: u32) -> Result<u32, Error> {
+    caller.require_auth();
+    
+    let Some(mut sweep) = storage::get_expiry_sweep(env) else {
+        return Ok(0);
+    };
+    let now = env.ledger().timestamp();
+    let mut visited = 0;
+    let mut expired = 0;
+    
+    // Entries are kept in the order they were indexed, and the sweep stops at
+    // the first one that has not expired yet. Later entries of the same week
+    // are marked once it has, at the latest when the week is over.
+    'buckets: while visited < limit && sweep.bucket * EXPIRY_BUCKET_SECONDS <= now {
+        let scope = IndexScope::Expiry(sweep.bucket);
+        let len = storage::get_index(env, &scope).len;
+        
+        for cert_id in storage::get_index_slots(env, &scope, sweep.index, limit - visited).iter() {
+            if let Some(certificate) = storage::get_certificate(env, &cert_id) {
+                if certificate.expiration_date.is_some_and(|date| date >= now) {
+                    break 'buckets;
+                }
+                if expire_certificate(env, certificate) {
+                    expired += 1;
+                }
+            }
+            sweep.index += 1;
+            visited += 1;
+        }
+        if sweep.index < len {
+            break;
+        }
+        
+        sweep.bucket += 1;
+        sweep.index = 0;
+        visited += 1;
+    }
+    
+    storage::set_expiry_sweep(env, &sweep);
+    pay_reward(env, &caller, expired);
+    
+    Ok(expired)
+}
+
+// Set the reward paid per expired certificate to callers of `process_expirations`,
+// or remove it
+pub fn set_expiry_reward(env: &Env, reward: Option<ExpiryReward>) -> Result<bool, Error> {
+    // Verify the caller is the admin
+    let admin = access_control::require_admin(env)?;
+    
+    let key = Symbol::new(env, EXPIRY_REWARD_KEY);
+    match &reward {
+        Some(reward) if reward.amount < 0 => return Err(Error::InvalidInput),
+        Some(reward) => env.storage().instance().set(&key, reward),
+        None => env.storage().instance().remove(&key),
+    }
+    
+    // Emit reward set event
+    env.events().publish(
+        (Symbol::new(env, EXPIRY_REWARD_SET_EVENT),),
+        (admin, reward),
+    );
+    
+    Ok(true)
+}
+
+// Get the reward paid per expired certificate, if one is set
+pub fn get_expiry_reward(env: &Env) -> Option<ExpiryReward> {
+    env.storage()
+        .instance()
+        .get(&Symbol::new(env, EXPIRY_REWARD_KEY))
+}
+
+// Mark a certificate past its expiration date as expired. Certificates that were
+// renewed, revoked or already marked are left alone.
+fn expire_certificate(env: &Env, mut certificate: Certificate) -> bool {
+    if !matches!(
+        certificate.status,
+        CertificateStatus::Active | CertificateStatus::Suspended
+    ) {
+        return false;
+    }
+    
+    certificate.status = CertificateStatus::Expired;
+    storage::set_certificate(env, &certificate);
+    
+    // Emit certificate expired event
+    env.events().publish(
+        (Symbol::new(env, CERTIFICATE_EXPIRED_EVENT),),
+        (certificate.id, certificate.owner, certificate.expiration_date),
+    );
+    
+    true
+}
+
+// Pay the caller of a sweep its reward from the contract's own balance of the
+// reward token, as far as the balance allows
+fn pay_reward(env: &Env, caller: &Address, expired: u32) {
+    let Some(reward) = get_expiry_reward(env) else {
+        return;
+    };
+    let client = token::Client::new(env, &reward.token);
+    let contract = env.current_contract_address();
+    
+    let owed = reward.amount.saturating_mul(expired as i128);
+    let amount = cmp::min(owed, client.balance(&contract));
+    if amount > 0 {
+        client.transfer(&contract, caller, &amount);
+    }
+}
+
+// Walk the buckets of a window, skipping the first `cursor` index slots and
+// reading at most `limit` more. Renewed and revoked certificates stay in the
+// index, as do the slots of certificates transferred away from an owner, and are
+// skipped here, so a page can hold fewer than `limit` certificates.
+fn list_expiring(
+    env: &Env,
+    from: u64,
+    to: u64,
+    cursor: u32,
+    limit: u32,
+    owner: Option<&Address>,
+    bucket_scope: impl Fn(u64) -> IndexScope,
+) -> Result<ExpiringCertificatePage, Error> {
+    let first_bucket = expiry_bucket(from);
+    let last_bucket = expiry_bucket(to);
+    if from > to || last_bucket - first_bucket >= MAX_EXPIRY_BUCKETS {
+        return Err(Error::InvalidExpiryWindow);
+    }
+    
+    let end = cursor.saturating_add(limit);
+    let mut certificates = Vec::new(env);
+    
+    // Number of index slots in the buckets before the current one
+    let mut position: u32 = 0;
+    
+    for bucket in first_bucket..=last_bucket {
+        let scope = bucket_scope(bucket);
+        let bucket_end = position + storage::get_index(env, &scope).len;
+        
+        let start = cursor.saturating_sub(position);
+        let cert_ids = storage::get_index_slots(
+            env,
+            &scope,
+            start,
+            (cmp::min(end, bucket_end) - position).saturating_sub(start),
+        );
+        for cert_id in cert_ids.iter() {
+            let Some(certificate) = storage::get_certificate(env, &cert_id) else {
+                continue;
+            };
+            let in_window = certificate
+                .expiration_date
+                .is_some_and(|date| date >= from && date <= to);
+            if !in_window || owner.is_some_and(|owner| *owner != certificate.owner) {
+                continue;
+            }
+            
+            if !matches!(
+                lifecycle::effective_status(env, &certificate),
+                CertificateStatus::Withdrawn | CertificateStatus::Superseded
+            ) {
+                certificates.push_back(lifecycle::with_effective_status(env, certificate));
+            }
+        }
+        
+        position = bucket_end;
+        if position >= end {
+            return Ok(ExpiringCertificatePage {
+                certificates,
+                next_cursor: if position > end || bucket < last_bucket {
+                    Some(end)
+                } else {
+                    None
+                },
+            });
+        }
+    }
+    
+    Ok(ExpiringCertificatePage {
+        certificates,
+        next_cursor: None,
+    })
+}
+
+// Get the expiry index bucket of a timestamp
+fn expiry_bucket(timestamp: u64) -> u64 {
+    timestamp / EXPIRY_BUCKET_SECONDS
+}
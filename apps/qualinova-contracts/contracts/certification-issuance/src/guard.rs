@@ -0,0 +1,67 @@
+use crate::{access_control, Error};
+use soroban_sdk::{contractclient, Address, Bytes, BytesN, Env, Symbol};
+
+// Instance storage key of the issuance guard address
+const ISSUANCE_GUARD_KEY: &str = "issuance_guard";
+
+// Events
+const ISSUANCE_GUARD_SET_EVENT: &str = "issuance_guard_set";
+
+// The issuance guard contract. The host aborts the invocation when one of these
+// checks fails, so they run in the guard, where a failure ends only the guard
+// call and comes back as an error.
+#[allow(dead_code)]
+#[contractclient(name = "IssuanceGuardClient")]
+pub trait IssuanceGuard {
+    fn verify_signature(env: Env, public_key: BytesN<32>, message: Bytes, signature: BytesN<64>);
+    fn authorize(env: Env, address: Address, content_hash: BytesN<32>);
+}
+
+// Set the issuance guard contract signatures and authorizations are checked with
+pub fn set_issuance_guard(env: &Env, guard: Address) -> Result<bool, Error> {
+    // Verify the caller is the admin
+    let admin = access_control::require_admin(env)?;
+    
+    env.storage()
+        .instance()
+        .set(&Symbol::new(env, ISSUANCE_GUARD_KEY), &guard);
+    
+    // Emit guard set event
+    env.events().publish(
+        (Symbol::new(env, ISSUANCE_GUARD_SET_EVENT),),
+        (admin, guard),
+    );
+    
+    Ok(true)
+}
+
+// Get the issuance guard contract address
+pub fn get_issuance_guard(env: &Env) -> Result<Address, Error> {
+    env.storage()
+        .instance()
+        .get(&Symbol::new(env, ISSUANCE_GUARD_KEY))
+        .ok_or(Error::IssuanceGuardNotSet)
+}
+
+// Check an ed25519 signature over a message without aborting when it does not match
+pub fn signature_matches(
+    env: &Env,
+    public_key: &BytesN<32>,
+    message: &Bytes,
+    signature: &BytesN<64>,
+) -> Result<bool, Error> {
+    let guard = get_issuance_guard(env)?;
+    let result = IssuanceGuardClient::new(env, &guard)
+        .try_verify_signature(public_key, message, signature);
+    
+    Ok(matches!(result, Ok(Ok(()))))
+}
+
+// Check that an address authorized the content with the given hash without
+// aborting when it did not
+pub fn is_authorized(env: &Env, address: &Address, content_hash: &BytesN<32>) -> Result<bool, Error> {
+    let guard = get_issuance_guard(env)?;
+    let result = IssuanceGuardClient::new(env, &guard).try_authorize(address, content_hash);
+    
+    Ok(matches!(result, Ok(Ok(()))))
+}
@@ -0,0 +1,83 @@
+use crate::storage::{self, IndexScope};
+use crate::{Certificate, CertificateId};
+use soroban_sdk::{Address, Env, Map, String, Vec};
+
+// Add a newly stored certificate to the indexes of its owner and issuer
+pub fn index_certificate(env: &Env, certificate: &Certificate) {
+    index_owner_certificate(env, certificate);
+    index_issued_certificates(env, &certificate.issuer, &Vec::from_array(env, [certificate.clone()]));
+}
+
+// Add a newly stored certificate to the indexes of its owner
+pub fn index_owner_certificate(env: &Env, certificate: &Certificate) {
+    for scope in owner_scopes(certificate, &certificate.owner) {
+        storage::add_to_index(env, &scope, &certificate.id);
+    }
+}
+
+// Add newly stored certificates of an issuer to the issuer's indexes. Each index
+// is appended to once, however many of the certificates it lists, so a batch
+// writes the issuer's pages once.
+pub fn index_issued_certificates(env: &Env, issuer: &Address, certificates: &Vec<Certificate>) {
+    let mut appends: Map<IndexScope, Vec<CertificateId>> = Map::new(env);
+    
+    for certificate in certificates.iter() {
+        let scopes = [
+            IndexScope::Issuer(issuer.clone()),
+            IndexScope::IssuerType(issuer.clone(), certificate.cert_type_id.clone()),
+            IndexScope::IssuerAchievement(issuer.clone(), certificate.metadata.achievement_type.clone()),
+        ];
+        for scope in scopes {
+            let mut cert_ids = appends.get(scope.clone()).unwrap_or_else(|| Vec::new(env));
+            cert_ids.push_back(certificate.id.clone());
+            appends.set(scope, cert_ids);
+        }
+    }
+    
+    for (scope, cert_ids) in appends.iter() {
+        storage::extend_index(env, &scope, &cert_ids);
+    }
+}
+
+// Move a certificate's owner index entries to its new owner
+pub fn move_owner(env: &Env, certificate: &Certificate, new_owner: &Address) {
+    for scope in owner_scopes(certificate, &certificate.owner) {
+        storage::remove_from_index(env, &scope, &certificate.id);
+    }
+    for scope in owner_scopes(certificate, new_owner) {
+        storage::add_to_index(env, &scope, &certificate.id);
+    }
+}
+
+// Move a certificate's achievement type index entries when an amendment
+// changes its achievement type
+pub fn change_achievement_type(env: &Env, certificate: &Certificate, achievement_type: &String) {
+    let current = &certificate.metadata.achievement_type;
+    if current == achievement_type {
+        return;
+    }
+    
+    for scope in achievement_scopes(certificate, current) {
+        storage::remove_from_index(env, &scope, &certificate.id);
+    }
+    for scope in achievement_scopes(certificate, achievement_type) {
+        storage::add_to_index(env, &scope, &certificate.id);
+    }
+}
+
+// Get the indexes a certificate is listed in for an owner
+fn owner_scopes(certificate: &Certificate, owner: &Address) -> [IndexScope; 3] {
+    [
+        IndexScope::Owner(owner.clone()),
+        IndexScope::OwnerType(owner.clone(), certificate.cert_type_id.clone()),
+        IndexScope::OwnerAchievement(owner.clone(), certificate.metadata.achievement_type.clone()),
+    ]
+}
+
+// Get the indexes a certificate is listed in for an achievement type
+fn achievement_scopes(certificate: &Certificate, achievement_type: &String) -> [IndexScope; 2] {
+    [
+        IndexScope::OwnerAchievement(certificate.owner.clone(), achievement_type.clone()),
+        IndexScope::IssuerAchievement(certificate.issuer.clone(), achievement_type.clone()),
+    ]
+}
@@ -1,59 +1,53 @@
-use crate::{access_control, Certificate, CertificateId, CertificateMetadata};
-use alloc::format;
-use soroban_sdk::{Address, Bytes, Env, Symbol, Vec};
+use crate::{
+    access_control, amendment, authorities, batch, certification_types, entities, evidence,
+    expiry, guard, index, signing_keys, storage, verification, BatchItemResult, BatchMode,
+    Certificate, CertificateId, CertificateMetadata, CertificateStatus, CertificateVersion,
+    CertificationType, Error, Evidence,
+};
+use soroban_sdk::{Address, Bytes, BytesN, Env, Map, String, Symbol, Vec};
 use soroban_sdk::xdr::ToXdr;
 
-// Storage keys format
-const CERTIFICATE_COUNT_KEY: &str = "cert_count";
-const OWNER_CERTS_PREFIX: &str = "owner_certs";
-const ISSUER_CERTS_PREFIX: &str = "issuer_certs";
-
 // Events
 const CERTIFICATE_ISSUED_EVENT: &str = "certificate_issued";
 const CERTIFICATES_BATCH_ISSUED_EVENT: &str = "certificates_batch_issued";
 
-// Issue a new certificate
+// Issue a new certificate of a registered certification type. The issuer commits
+// to the metadata fields off-chain and submits their Merkle root, which its
+// signature covers.
 pub fn issue_certificate(
     env: &Env,
+    issuer: Address,
     owner: Address,
+    cert_type_id: String,
     metadata: CertificateMetadata,
+    metadata_root: BytesN<32>,
     expiration_date: Option<u64>,
-    signature: Bytes,
-) -> CertificateId {
+    evidence: Vec<Evidence>,
+    signing_key: BytesN<32>,
+    signature: BytesN<64>,
+) -> Result<CertificateId, Error> {
     // Verify the caller is an authorized issuer
-    let issuer = access_control::require_issuer(env);
+    access_control::require_issuer(env, &issuer)?;
     
     // Require authorization from the owner
     owner.require_auth();
     
-    // Generate a unique certificate ID
-    let cert_id = generate_certificate_id(env, &owner, &issuer, &metadata);
-    
-    // Create the certificate
-    let certificate = Certificate {
-        id: cert_id.clone(),
-        owner: owner.clone(),
-        issuer: issuer.clone(),
-        metadata: metadata.clone(),
-        issuance_date: env.ledger().timestamp(),
+    let cert_type = get_issuable_certification_type(env, &issuer, &cert_type_id)?;
+    let checked = check_certificate(
+        env,
+        &issuer,
+        &owner,
+        &cert_type,
+        metadata,
+        metadata_root,
         expiration_date,
-        revoked: false,
+        evidence,
+        signing_key,
         signature,
-    };
-    
-    // Store the certificate
-    let cert_id_bytes = env.crypto().sha256(&Bytes::from_slice(env, &cert_id.0.to_array()));
-    let certificate_key = get_certificate_key(env, &cert_id_bytes);
-    env.storage().persistent().set(&certificate_key, &certificate);
-    
-    // Update certificate counts
-    increment_certificate_count(env);
-    
-    // Add to owner's certificates
-    add_to_owner_certificates(env, &owner, &cert_id);
-    
-    // Add to issuer's certificates
-    add_to_issuer_certificates(env, &issuer, &cert_id);
+    )?;
+    let certificate = store_certificate(env, checked)?;
+    let cert_id = certificate.id.clone();
+    index::index_issued_certificates(env, &issuer, &Vec::from_array(env, [certificate]));
     
     // Emit certificate issued event
     env.events().publish(
@@ -61,78 +55,221 @@ pub fn issue_certificate(
         (cert_id.clone(), owner, issuer),
     );
     
-    cert_id
+    Ok(cert_id)
 }
 
-// Batch issue multiple certificates
+// Batch issue multiple certificates. In all-or-nothing mode the first failure
+// aborts the batch; in best-effort mode failed items are reported and skipped.
+//
+// Each owner authorizes their certificate through the issuance guard, with the
+// same message the issuer signs, rather than the batch call itself. A missing
+// authorization then fails only that item, as a bad signature does.
 pub fn batch_issue_certificates(
     env: &Env,
+    issuer: Address,
     owners: Vec<Address>,
+    cert_type_ids: Vec<String>,
     metadatas: Vec<CertificateMetadata>,
+    metadata_roots: Vec<BytesN<32>>,
     expiration_dates: Vec<Option<u64>>,
-    signatures: Vec<Bytes>,
-) -> Vec<CertificateId> {
+    evidence_bundles: Vec<Vec<Evidence>>,
+    signing_keys: Vec<BytesN<32>>,
+    signatures: Vec<BytesN<64>>,
+    mode: BatchMode,
+) -> Result<Vec<BatchItemResult>, Error> {
     // Verify the caller is an authorized issuer
-    let issuer = access_control::require_issuer(env);
+    access_control::require_issuer(env, &issuer)?;
     
     // Validate input arrays have the same length
     let count = owners.len();
-    if metadatas.len() != count || expiration_dates.len() != count || signatures.len() != count {
-        panic!("Input arrays must have the same length");
+    if cert_type_ids.len() != count
+        || metadatas.len() != count
+        || metadata_roots.len() != count
+        || expiration_dates.len() != count
+        || evidence_bundles.len() != count
+        || signing_keys.len() != count
+        || signatures.len() != count
+    {
+        return Err(Error::InputLengthMismatch);
     }
+    batch::check_batch_size(count)?;
     
-    let mut certificate_ids = Vec::new(env);
+    let mut results = Vec::new(env);
+    let mut certificates = Vec::new(env);
+    
+    // Cohorts usually share a few certification types, so each type is looked up
+    // in the registries once per batch
+    let mut cert_types: Map<String, CertificationType> = Map::new(env);
     
     // Process each certificate
     for i in 0..count {
-        let owner = owners.get(i).unwrap();
-        let metadata = metadatas.get(i).unwrap();
-        let expiration_date = expiration_dates.get(i).unwrap();
-        let signature = signatures.get(i).unwrap();
-        
-        // Require authorization from each owner
-        owner.require_auth();
-        
-        // Generate a unique certificate ID
-        let cert_id = generate_certificate_id(env, &owner, &issuer, &metadata);
-        
-        // Create the certificate
-        let certificate = Certificate {
-            id: cert_id.clone(),
-            owner: owner.clone(),
-            issuer: issuer.clone(),
-            metadata: metadata.clone(),
-            issuance_date: env.ledger().timestamp(),
-            expiration_date,
-            revoked: false,
-            signature,
+        let cert_type_id = cert_type_ids.get(i).unwrap();
+        let cert_type = match cert_types.get(cert_type_id.clone()) {
+            Some(cert_type) => Ok(cert_type),
+            None => get_issuable_certification_type(env, &issuer, &cert_type_id)
+                .inspect(|cert_type| cert_types.set(cert_type_id, cert_type.clone())),
         };
         
-        // Store the certificate
-        let cert_id_bytes = env.crypto().sha256(&Bytes::from_slice(env, &cert_id.0.to_array()));
-        let certificate_key = get_certificate_key(env, &cert_id_bytes);
-        env.storage().persistent().set(&certificate_key, &certificate);
-        
-        // Update certificate counts
-        increment_certificate_count(env);
-        
-        // Add to owner's certificates
-        add_to_owner_certificates(env, &owner, &cert_id);
-        
-        // Add to issuer's certificates
-        add_to_issuer_certificates(env, &issuer, &cert_id);
+        let owner = owners.get(i).unwrap();
+        let checked = cert_type.and_then(|cert_type| {
+            check_certificate(
+                env,
+                &issuer,
+                &owner,
+                &cert_type,
+                metadatas.get(i).unwrap(),
+                metadata_roots.get(i).unwrap(),
+                expiration_dates.get(i).unwrap(),
+                evidence_bundles.get(i).unwrap(),
+                signing_keys.get(i).unwrap(),
+                signatures.get(i).unwrap(),
+            )
+        });
+        let result = checked.and_then(|checked| {
+            if !guard::is_authorized(env, &owner, &checked.version.content_hash)? {
+                return Err(Error::Unauthorized);
+            }
+            store_certificate(env, checked)
+        });
         
-        // Add to the result list
-        certificate_ids.push_back(cert_id);
+        let result = result.map(|certificate| {
+            certificates.push_back(certificate.clone());
+            certificate.id
+        });
+        batch::record_result(&mut results, result, mode)?;
     }
     
+    // Add the issued certificates to the issuer's indexes in one go
+    index::index_issued_certificates(env, &issuer, &certificates);
+    
     // Emit batch issued event
+    let mut certificate_ids = Vec::new(env);
+    for certificate in certificates.iter() {
+        certificate_ids.push_back(certificate.id);
+    }
     env.events().publish(
         (Symbol::new(env, CERTIFICATES_BATCH_ISSUED_EVENT),),
-        (certificate_ids.clone(), issuer),
+        (certificate_ids, issuer),
     );
     
-    certificate_ids
+    Ok(results)
+}
+
+// Look up a certification type the issuer may issue: it must be registered and
+// active, and the issuer must be an active authority accredited for it
+pub fn get_issuable_certification_type(
+    env: &Env,
+    issuer: &Address,
+    cert_type_id: &String,
+) -> Result<CertificationType, Error> {
+    let cert_type = certification_types::get_active_certification_type(env, cert_type_id)?;
+    authorities::require_accredited(env, issuer, cert_type_id)?;
+    
+    Ok(cert_type)
+}
+
+// A certificate that passed every check at issuance, with what is stored
+// alongside it
+struct CheckedCertificate {
+    certificate: Certificate,
+    version: CertificateVersion, // the content the issuer signed
+    metadata_root: BytesN<32>,
+    evidence: Vec<Evidence>,
+    entity_registry: Option<Address>,
+}
+
+// Create and check a single certificate for an authorized issuer. Nothing is
+// written, so a failed certificate leaves no trace.
+//
+// Without an explicit expiration date the certificate expires after its type's
+// validity period. The issuer's signature covers the expiration date as submitted,
+// so a defaulted date is recorded with version 1 for verification. The evidence
+// bundle must cover every item the certification type requires.
+fn check_certificate(
+    env: &Env,
+    issuer: &Address,
+    owner: &Address,
+    cert_type: &CertificationType,
+    metadata: CertificateMetadata,
+    metadata_root: BytesN<32>,
+    expiration_date: Option<u64>,
+    evidence: Vec<Evidence>,
+    signing_key: BytesN<32>,
+    signature: BytesN<64>,
+) -> Result<CheckedCertificate, Error> {
+    // The evidence must cover everything the type requires
+    evidence::check_evidence(cert_type, &evidence)?;
+    
+    // Generate a unique certificate ID
+    let cert_id = generate_certificate_id(env, owner, issuer, &metadata);
+    if storage::has_certificate(env, &cert_id) {
+        return Err(Error::CertificateAlreadyExists);
+    }
+    
+    // Resolve the expiration date
+    let issuance_date = env.ledger().timestamp();
+    let resolved_expiration = match expiration_date {
+        Some(expiration) => Some(expiration),
+        None => certification_types::default_expiration(cert_type, issuance_date)?,
+    };
+    
+    // Create the certificate and check the issuer's signature over it
+    let certificate = Certificate {
+        id: cert_id,
+        owner: owner.clone(),
+        issuer: issuer.clone(),
+        cert_type_id: cert_type.cert_type_id.clone(),
+        metadata,
+        issuance_date,
+        expiration_date: resolved_expiration,
+        status: CertificateStatus::Active,
+        signature,
+        signing_key: check_signing_key(env, issuer, signing_key)?,
+    };
+    let entity_registry = entities::owner_entity_registry(env, owner)?;
+    let version =
+        amendment::issued_version(env, &certificate, Some(&metadata_root), expiration_date);
+    verification::check_version_signature(env, &version)?;
+    
+    Ok(CheckedCertificate {
+        certificate,
+        version,
+        metadata_root,
+        evidence,
+        entity_registry,
+    })
+}
+
+// Store a checked certificate and add it to its owner's indexes. The caller adds
+// it to the issuer's indexes.
+fn store_certificate(env: &Env, checked: CheckedCertificate) -> Result<Certificate, Error> {
+    let certificate = checked.certificate;
+    let cert_id = certificate.id.clone();
+    
+    // Record the certificate with the owner's entity first, as the entity
+    // contract can still refuse it
+    if let Some(registry) = checked.entity_registry {
+        entities::record_certification(env, &registry, &certificate.owner, &cert_id)?;
+    }
+    
+    // Keep the signed content as version 1 if the expiration date was defaulted
+    if checked.version.expiration_date != certificate.expiration_date {
+        storage::add_certificate_version(env, &cert_id, &checked.version);
+    }
+    
+    // Store the certificate, its metadata root and its evidence
+    storage::set_certificate(env, &certificate);
+    storage::set_metadata_root(env, &cert_id, &checked.metadata_root);
+    storage::set_evidence(env, &cert_id, &checked.evidence);
+    
+    // Update certificate counts
+    storage::increment_certificate_count(env);
+    
+    // Add to the owner's certificate indexes and the expiry index
+    index::index_owner_certificate(env, &certificate);
+    expiry::index_expiration(env, &certificate);
+    
+    Ok(certificate)
 }
 
 // Generate a unique certificate ID
@@ -186,76 +323,23 @@ pub fn generate_certificate_id(
     CertificateId(hash.into())
 }
 
-// Increment the total certificate count
-fn increment_certificate_count(env: &Env) {
-    let count_key = Symbol::new(env, CERTIFICATE_COUNT_KEY);
-    let current_count: u32 = env.storage().instance().get(&count_key).unwrap_or(0);
-    env.storage().instance().set(&count_key, &(current_count + 1));
+// Check that a key the issuer signed with is registered to it and may sign right
+// now. While rotation windows overlap, any of the valid keys may be used.
+pub fn check_signing_key(
+    env: &Env,
+    issuer: &Address,
+    public_key: BytesN<32>,
+) -> Result<BytesN<32>, Error> {
+    let key = signing_keys::find_signing_key(env, issuer, &public_key)
+        .ok_or(Error::SigningKeyNotFound)?;
+    if !signing_keys::is_valid_at(&key, env.ledger().timestamp()) {
+        return Err(Error::NoActiveSigningKey);
+    }
+    
+    Ok(public_key)
 }
 
 // Get the total certificate count
 pub fn get_certificate_count(env: &Env) -> u32 {
-    let count_key = Symbol::new(env, CERTIFICATE_COUNT_KEY);
-    env.storage().instance().get(&count_key).unwrap_or(0)
-}
-
-// Add a certificate to the owner's list
-fn add_to_owner_certificates(env: &Env, owner: &Address, cert_id: &CertificateId) {
-    let owner_key = get_owner_key(env, owner);
-    
-    let mut owner_certs: Vec<CertificateId> = env
-        .storage()
-        .persistent()
-        .get(&owner_key)
-        .unwrap_or_else(|| Vec::new(env));
-    
-    owner_certs.push_back(cert_id.clone());
-    env.storage().persistent().set(&owner_key, &owner_certs);
-}
-
-// Add a certificate to the issuer's list
-fn add_to_issuer_certificates(env: &Env, issuer: &Address, cert_id: &CertificateId) {
-    let issuer_key = get_issuer_key(env, issuer);
-    
-    let mut issuer_certs: Vec<CertificateId> = env
-        .storage()
-        .persistent()
-        .get(&issuer_key)
-        .unwrap_or_else(|| Vec::new(env));
-    
-    issuer_certs.push_back(cert_id.clone());
-    env.storage().persistent().set(&issuer_key, &issuer_certs);
-}
-
-// Get all certificates for an owner
-pub fn get_owner_certificates(env: &Env, owner: &Address) -> Vec<CertificateId> {
-    let owner_key = get_owner_key(env, owner);
-    
-    env.storage()
-        .persistent()
-        .get(&owner_key)
-        .unwrap_or_else(|| Vec::new(env))
-}
-
-// Helper function to get a certificate key from a hash
-fn get_certificate_key(env: &Env, hash: &soroban_sdk::crypto::Hash<32>) -> Symbol {
-    // Create a simple key with the hash - avoid using colons or other special characters
-    let key = format!("cert{:x}", hash.to_array()[0]);
-    Symbol::new(env, &key)
-}
-
-// Helper function to get an owner key
-fn get_owner_key(env: &Env, owner: &Address) -> Symbol {
-    let owner_hash = env.crypto().sha256(&owner.clone().to_xdr(env));
-    // Avoid using colons in Symbol keys
-    let key = format!("{}_{:x}", OWNER_CERTS_PREFIX, owner_hash.to_array()[0]);
-    Symbol::new(env, &key)
-}
-
-// Helper function to get an issuer key
-fn get_issuer_key(env: &Env, issuer: &Address) -> Symbol {
-    let issuer_hash = env.crypto().sha256(&issuer.clone().to_xdr(env));
-    // Avoid using colons in Symbol keys
-    let key = format!("{}_{:x}", ISSUER_CERTS_PREFIX, issuer_hash.to_array()[0]);
-    Symbol::new(env, &key)
+    storage::get_certificate_count(env)
 }
@@ -1,5 +1,8 @@
 // Disable the standard library for Soroban compatibility
 #![no_std]
+// Issuance entrypoints take every certificate field as its own argument, and the
+// argument helpers generated for them cannot carry a lint attribute
+#![allow(clippy::too_many_arguments)]
 
 // Import external crates
 extern crate alloc;
@@ -14,9 +17,29 @@ mod wasm_allocator {
 
 // Import modules
 mod access_control;
+mod amendment;
+mod authorities;
+mod batch;
+mod certification_types;
+mod disclosure;
+mod entities;
+mod error;
+mod evidence;
+mod expiry;
+mod guard;
+mod index;
 mod issuance;
+mod lifecycle;
+mod migration;
+mod offer;
+mod qr_payload;
 mod query;
+mod renewal;
+mod revocation;
+mod signing_keys;
+mod storage;
 mod transfer;
+mod upgrade;
 mod verification;
 #[cfg(test)]
 mod test;
@@ -26,6 +49,11 @@ use soroban_sdk::{
     contract, contractimpl, contracttype, Address, Bytes, BytesN, Env, Map, String, Vec,
 };
 
+pub use authorities::{Authority, AuthorityStatus};
+pub use certification_types::{CertTypeStatus, CertificationType};
+pub use entities::{Entity, EntityStatus};
+pub use error::Error;
+
 // Define the certificate ID type
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -41,6 +69,26 @@ pub struct CertificateMetadata {
     pub additional_data: Map<String, BytesN<32>>,
 }
 
+// A piece of evidence submitted at issuance: the SHA-256 of the document and
+// where it can be retrieved off-chain
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Evidence {
+    pub name: String, // Name from the certification type's required evidence
+    pub document_hash: BytesN<32>,
+    pub uri: Option<String>,
+}
+
+// Define a single metadata field, as disclosed to a verifier
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DisclosedField {
+    Title(String),
+    Description(String),
+    AchievementType(String),
+    AdditionalData(String, BytesN<32>),
+}
+
 // Define the certificate structure
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -48,11 +96,210 @@ pub struct Certificate {
     pub id: CertificateId,
     pub owner: Address,
     pub issuer: Address,
+    pub cert_type_id: String, // ID in the certification-type-management contract
     pub metadata: CertificateMetadata,
     pub issuance_date: u64,
     pub expiration_date: Option<u64>,
+    pub status: CertificateStatus,
+    pub signature: BytesN<64>,
+    pub signing_key: BytesN<32>,
+}
+
+// Define one signed version of a certificate's content. Version 1 is the
+// content signed at issuance; every amendment adds the next version.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CertificateVersion {
+    pub version: u32,
+    pub owner: Address, // the owner the version was signed for
+    pub metadata: CertificateMetadata,
+    pub expiration_date: Option<u64>,
+    pub content_hash: BytesN<32>,
+    pub signature: BytesN<64>,
+    pub signing_key: BytesN<32>,
+    pub recorded_at: u64,
+    pub recorded_by: Address, // the issuer or admin who signed the version
+    pub note: String,
+}
+
+// Define the lifecycle status of a certificate
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CertificateStatus {
+    Active,
+    Suspended,
+    Withdrawn,
+    Expired,
+    Superseded,
+}
+
+// Define a temporary suspension of a certificate
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Suspension {
+    pub reason: String,
+    pub suspended_by: Address,
+    pub suspended_at: u64,
+    pub lift_at: Option<u64>, // None until reinstated explicitly
+}
+
+// Define who may move a certificate to a new owner
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TransferPolicy {
+    NonTransferable,
+    OwnerTransferable,
+    IssuerApprovalRequired,
+}
+
+// Define a pending offer to transfer a certificate
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TransferOffer {
+    pub certificate_id: CertificateId,
+    pub from: Address,
+    pub to: Address,
+    pub offered_at: u64,
+    pub expires_at: u64,
+}
+
+// Define a pending handover of the admin role. Without an expiry the proposal
+// stays open until it is accepted, cancelled or replaced.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminProposal {
+    pub proposed_admin: Address,
+    pub proposed_at: u64,
+    pub expires_at: Option<u64>,
+}
+
+// Define an issuer's ed25519 signing key and its validity window
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SigningKey {
+    pub public_key: BytesN<32>,
+    pub valid_from: u64,
+    pub valid_until: Option<u64>,
     pub revoked: bool,
-    pub signature: Bytes,
+}
+
+// Define why a certificate was revoked
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RevocationReason {
+    Superseded,
+    Fraud,
+    NonConformity,
+    HolderRequest,
+}
+
+// Define a revocation entry in the registry
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Revocation {
+    pub certificate_id: CertificateId,
+    pub reason: RevocationReason,
+    pub note: String,
+    pub revoked_by: Address,
+    pub effective_date: u64,
+    pub recorded_at: u64,
+}
+
+// Define a page of the revocation registry
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RevocationPage {
+    pub revocations: Vec<Revocation>,
+    pub next_cursor: Option<u32>, // None once the end of the registry is reached
+}
+
+// Page of certificates from an owner or issuer index
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CertificatePage {
+    pub certificates: Vec<Certificate>,
+    pub next_cursor: Option<u32>, // None once the end of the index is reached
+}
+
+// Order of a filtered certificate listing: the order certificates were added to
+// the index, which for an issuer is the order they were issued in
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CertificateOrder {
+    OldestFirst,
+    NewestFirst,
+}
+
+// Filter of a certificate listing. Unset fields match every certificate.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CertificateFilter {
+    pub statuses: Vec<CertificateStatus>, // effective statuses; empty for any
+    pub cert_type_id: Option<String>,
+    pub achievement_type: Option<String>,
+    pub issued_from: Option<u64>, // inclusive
+    pub issued_to: Option<u64>,   // inclusive
+    pub order: CertificateOrder,
+}
+
+// Reward paid to expiry sweep callers for each certificate they mark expired
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExpiryReward {
+    pub token: Address,
+    pub amount: i128,
+}
+
+// Page of certificates from the expiry index
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExpiringCertificatePage {
+    pub certificates: Vec<Certificate>,
+    pub next_cursor: Option<u32>, // None once the end of the window is reached
+}
+
+// Define how a batch handles items that fail
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BatchMode {
+    AllOrNothing, // The first failure aborts the whole batch
+    BestEffort,   // Failed items are reported and the rest go ahead
+}
+
+// Define the result of one item of a batch
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BatchItemResult {
+    Success(CertificateId),
+    Failure(u32), // Error code of the failed item
+}
+
+// Define the outcome of a certificate verification
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum VerificationStatus {
+    Valid,
+    Expired,
+    Revoked,
+    Suspended,
+    Superseded,
+    BadSignature,
+    IssuerInactive,
+    NotFound,
+}
+
+// Define the detailed result of a certificate verification
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VerificationReport {
+    pub certificate_id: CertificateId,
+    pub status: VerificationStatus,
+    pub checked_at: u64,
+    pub issuer: Option<Address>,
+    pub issuer_active: bool,
+    pub signing_key: BytesN<32>, // all zeros when the certificate was not found
+    pub issuance_date: Option<u64>,
+    pub expiration_date: Option<u64>,
 }
 
 // Define the contract
@@ -65,30 +312,51 @@ impl CertificationContract {
     // === Initialization ===
     
     // Initialize the contract with an admin
-    pub fn initialize(env: &Env, admin: Address) -> bool {
-        access_control::initialize(env, &admin);
-        true
+    pub fn initialize(env: &Env, admin: Address) -> Result<bool, Error> {
+        access_control::initialize(env, &admin)?;
+        upgrade::set_schema_version(env);
+        Ok(true)
     }
     
     // === Access Control ===
     
     // Get the admin address
-    pub fn get_admin(env: &Env) -> Address {
+    pub fn get_admin(env: &Env) -> Result<Address, Error> {
         access_control::get_admin(env)
     }
     
-    // Transfer admin role to a new address
-    pub fn transfer_admin(env: &Env, new_admin: Address) -> bool {
-        access_control::transfer_admin(env, new_admin)
+    // Propose a new admin. The role only changes hands once the new admin
+    // accepts; a new proposal replaces any pending one.
+    pub fn propose_admin(
+        env: &Env,
+        new_admin: Address,
+        expires_at: Option<u64>,
+    ) -> Result<bool, Error> {
+        access_control::propose_admin(env, new_admin, expires_at)
+    }
+    
+    // Accept the pending admin proposal as the proposed admin
+    pub fn accept_admin(env: &Env) -> Result<bool, Error> {
+        access_control::accept_admin(env)
+    }
+    
+    // Withdraw the pending admin proposal
+    pub fn cancel_admin_proposal(env: &Env) -> Result<bool, Error> {
+        access_control::cancel_admin_proposal(env)
+    }
+    
+    // Get the pending admin proposal
+    pub fn get_admin_proposal(env: &Env) -> Result<AdminProposal, Error> {
+        access_control::get_admin_proposal(env)
     }
     
     // Add an issuer
-    pub fn add_issuer(env: &Env, issuer: Address) -> bool {
+    pub fn add_issuer(env: &Env, issuer: Address) -> Result<bool, Error> {
         access_control::add_issuer(env, issuer)
     }
     
     // Remove an issuer
-    pub fn remove_issuer(env: &Env, issuer: Address) -> bool {
+    pub fn remove_issuer(env: &Env, issuer: Address) -> Result<bool, Error> {
         access_control::remove_issuer(env, issuer)
     }
     
@@ -102,28 +370,134 @@ impl CertificationContract {
         access_control::get_issuers(env)
     }
     
+    // === Certification Types ===
+    
+    // Set the certification-type-management contract issuance checks against
+    pub fn set_certification_type_registry(env: &Env, registry: Address) -> Result<bool, Error> {
+        certification_types::set_certification_type_registry(env, registry)
+    }
+    
+    // Get the certification-type-management contract address
+    pub fn get_certification_type_registry(env: &Env) -> Result<Address, Error> {
+        certification_types::get_certification_type_registry(env)
+    }
+    
+    // === Authorities ===
+    
+    // Set the authority contract issuers' accreditation is checked against
+    pub fn set_authority_registry(env: &Env, registry: Address) -> Result<bool, Error> {
+        authorities::set_authority_registry(env, registry)
+    }
+    
+    // Get the authority contract address
+    pub fn get_authority_registry(env: &Env) -> Result<Address, Error> {
+        authorities::get_authority_registry(env)
+    }
+    
+    // === Entities ===
+    
+    // Set the entity contract certificates issued to registered entities are recorded in
+    pub fn set_entity_registry(env: &Env, registry: Address) -> Result<bool, Error> {
+        entities::set_entity_registry(env, registry)
+    }
+    
+    // Get the entity contract address
+    pub fn get_entity_registry(env: &Env) -> Result<Address, Error> {
+        entities::get_entity_registry(env)
+    }
+    
+    // === Issuance Guard ===
+    
+    // Set the issuance guard contract signatures are checked with
+    pub fn set_issuance_guard(env: &Env, guard: Address) -> Result<bool, Error> {
+        guard::set_issuance_guard(env, guard)
+    }
+    
+    // Get the issuance guard contract address
+    pub fn get_issuance_guard(env: &Env) -> Result<Address, Error> {
+        guard::get_issuance_guard(env)
+    }
+    
+    // === Signing Keys ===
+    
+    // Register an ed25519 key the issuer signs certificates with
+    pub fn register_signing_key(
+        env: &Env,
+        issuer: Address,
+        public_key: BytesN<32>,
+        valid_from: u64,
+        valid_until: Option<u64>,
+    ) -> Result<bool, Error> {
+        signing_keys::register_signing_key(env, issuer, public_key, valid_from, valid_until)
+    }
+    
+    // Revoke a compromised signing key
+    pub fn revoke_signing_key(env: &Env, issuer: Address, public_key: BytesN<32>) -> Result<bool, Error> {
+        signing_keys::revoke_signing_key(env, issuer, public_key)
+    }
+    
+    // Get all signing keys of an issuer
+    pub fn get_signing_keys(env: &Env, issuer: Address) -> Vec<SigningKey> {
+        signing_keys::get_signing_keys(env, issuer)
+    }
+    
     // === Certificate Issuance ===
     
-    // Issue a new certificate
+    // Issue a new certificate of a registered certification type. Without an
+    // expiration date, the type's validity period applies.
     pub fn issue_certificate(
         env: &Env,
+        issuer: Address,
         owner: Address,
+        cert_type_id: String,
         metadata: CertificateMetadata,
+        metadata_root: BytesN<32>,
         expiration_date: Option<u64>,
-        signature: Bytes,
-    ) -> CertificateId {
-        issuance::issue_certificate(env, owner, metadata, expiration_date, signature)
+        evidence: Vec<Evidence>,
+        signing_key: BytesN<32>,
+        signature: BytesN<64>,
+    ) -> Result<CertificateId, Error> {
+        issuance::issue_certificate(
+            env,
+            issuer,
+            owner,
+            cert_type_id,
+            metadata,
+            metadata_root,
+            expiration_date,
+            evidence,
+            signing_key,
+            signature,
+        )
     }
     
     // Batch issue multiple certificates
     pub fn batch_issue_certificates(
         env: &Env,
+        issuer: Address,
         owners: Vec<Address>,
+        cert_type_ids: Vec<String>,
         metadatas: Vec<CertificateMetadata>,
+        metadata_roots: Vec<BytesN<32>>,
         expiration_dates: Vec<Option<u64>>,
-        signatures: Vec<Bytes>,
-    ) -> Vec<CertificateId> {
-        issuance::batch_issue_certificates(env, owners, metadatas, expiration_dates, signatures)
+        evidence_bundles: Vec<Vec<Evidence>>,
+        signing_keys: Vec<BytesN<32>>,
+        signatures: Vec<BytesN<64>>,
+        mode: BatchMode,
+    ) -> Result<Vec<BatchItemResult>, Error> {
+        issuance::batch_issue_certificates(
+            env,
+            issuer,
+            owners,
+            cert_type_ids,
+            metadatas,
+            metadata_roots,
+            expiration_dates,
+            evidence_bundles,
+            signing_keys,
+            signatures,
+            mode,
+        )
     }
     
     // Get the total certificate count
@@ -131,31 +505,84 @@ impl CertificationContract {
         issuance::get_certificate_count(env)
     }
     
+    // === Certificate Renewal ===
+    
+    // Renew a certificate, issuing a successor linked to it
+    pub fn renew_certificate(
+        env: &Env,
+        certificate_id: CertificateId,
+        new_expiration: Option<u64>,
+        signing_key: BytesN<32>,
+        signature: BytesN<64>,
+    ) -> Result<CertificateId, Error> {
+        renewal::renew_certificate(env, certificate_id, new_expiration, signing_key, signature)
+    }
+    
+    // Get the certificate a renewal replaced
+    pub fn get_predecessor(env: &Env, certificate_id: CertificateId) -> Option<CertificateId> {
+        renewal::get_predecessor(env, certificate_id)
+    }
+    
+    // Get the certificate that renewed this one
+    pub fn get_successor(env: &Env, certificate_id: CertificateId) -> Option<CertificateId> {
+        renewal::get_successor(env, certificate_id)
+    }
+    
+    // Get the whole renewal chain a certificate belongs to, oldest first
+    pub fn get_renewal_chain(
+        env: &Env,
+        certificate_id: CertificateId,
+    ) -> Result<Vec<CertificateId>, Error> {
+        renewal::get_renewal_chain(env, certificate_id)
+    }
+    
     // === Certificate Queries ===
     
     // Get a certificate by its ID
-    pub fn get_certificate(env: &Env, certificate_id: CertificateId) -> Certificate {
+    pub fn get_certificate(env: &Env, certificate_id: CertificateId) -> Result<Certificate, Error> {
         query::get_certificate(env, certificate_id)
     }
     
-    // List certificates by owner with pagination
+    // List certificates by owner, paged by index cursor
     pub fn list_certificates_by_owner(
         env: &Env,
         owner: Address,
-        start_index: u32,
+        cursor: u32,
         limit: u32,
-    ) -> Vec<Certificate> {
-        query::list_certificates_by_owner(env, owner, start_index, limit)
+    ) -> CertificatePage {
+        query::list_certificates_by_owner(env, owner, cursor, limit)
     }
     
-    // List certificates by issuer with pagination
+    // List certificates by issuer, paged by index cursor
     pub fn list_certificates_by_issuer(
         env: &Env,
         issuer: Address,
-        start_index: u32,
+        cursor: u32,
         limit: u32,
-    ) -> Vec<Certificate> {
-        query::list_certificates_by_issuer(env, issuer, start_index, limit)
+    ) -> CertificatePage {
+        query::list_certificates_by_issuer(env, issuer, cursor, limit)
+    }
+    
+    // List an owner's certificates matching a filter, paged by index cursor
+    pub fn filter_certificates_by_owner(
+        env: &Env,
+        owner: Address,
+        filter: CertificateFilter,
+        cursor: Option<u32>,
+        limit: u32,
+    ) -> Result<CertificatePage, Error> {
+        query::filter_certificates_by_owner(env, owner, filter, cursor, limit)
+    }
+    
+    // List an issuer's certificates matching a filter, paged by index cursor
+    pub fn filter_certificates_by_issuer(
+        env: &Env,
+        issuer: Address,
+        filter: CertificateFilter,
+        cursor: Option<u32>,
+        limit: u32,
+    ) -> Result<CertificatePage, Error> {
+        query::filter_certificates_by_issuer(env, issuer, filter, cursor, limit)
     }
     
     // Count certificates by owner
@@ -168,6 +595,29 @@ impl CertificationContract {
         query::count_certificates_by_issuer(env, &issuer)
     }
     
+    // List certificates expiring between `from` and `to`, paged by index cursor
+    pub fn list_expiring_certificates(
+        env: &Env,
+        from: u64,
+        to: u64,
+        cursor: u32,
+        limit: u32,
+    ) -> Result<ExpiringCertificatePage, Error> {
+        expiry::list_expiring_certificates(env, from, to, cursor, limit)
+    }
+    
+    // List an owner's certificates expiring between `from` and `to`, paged by index cursor
+    pub fn list_owner_expiring_certificates(
+        env: &Env,
+        owner: Address,
+        from: u64,
+        to: u64,
+        cursor: u32,
+        limit: u32,
+    ) -> Result<ExpiringCertificatePage, Error> {
+        expiry::list_owner_expiring_certificates(env, owner, from, to, cursor, limit)
+    }
+    
     // === Certificate Transfer ===
     
     // Transfer a certificate to a new owner
@@ -175,10 +625,88 @@ impl CertificationContract {
         env: &Env,
         certificate_id: CertificateId,
         new_owner: Address,
-    ) -> bool {
+    ) -> Result<bool, Error> {
         transfer::transfer_certificate(env, certificate_id, new_owner)
     }
     
+    // Transfer several certificates, each to its own new owner
+    pub fn batch_transfer_certificates(
+        env: &Env,
+        certificate_ids: Vec<CertificateId>,
+        new_owners: Vec<Address>,
+        mode: BatchMode,
+    ) -> Result<Vec<BatchItemResult>, Error> {
+        batch::batch_transfer_certificates(env, certificate_ids, new_owners, mode)
+    }
+    
+    // Offer a certificate to a new owner, who can accept until `expires_at`
+    pub fn offer_transfer(
+        env: &Env,
+        certificate_id: CertificateId,
+        to: Address,
+        expires_at: u64,
+    ) -> Result<bool, Error> {
+        offer::offer_transfer(env, certificate_id, to, expires_at)
+    }
+    
+    // Accept a pending transfer offer as its recipient
+    pub fn accept_transfer(
+        env: &Env,
+        recipient: Address,
+        certificate_id: CertificateId,
+    ) -> Result<bool, Error> {
+        offer::accept_transfer(env, recipient, certificate_id)
+    }
+    
+    // Cancel or decline a pending transfer offer
+    pub fn cancel_transfer(
+        env: &Env,
+        caller: Address,
+        certificate_id: CertificateId,
+    ) -> Result<bool, Error> {
+        offer::cancel_transfer(env, caller, certificate_id)
+    }
+    
+    // Get the pending transfer offer for a certificate
+    pub fn get_transfer_offer(
+        env: &Env,
+        certificate_id: CertificateId,
+    ) -> Result<TransferOffer, Error> {
+        offer::get_transfer_offer(env, certificate_id)
+    }
+    
+    // Get the unexpired transfer offers made by or to an address
+    pub fn get_pending_offers(env: &Env, address: Address) -> Vec<TransferOffer> {
+        offer::get_pending_offers(env, address)
+    }
+    
+    // Set the transfer policy of a single certificate
+    pub fn set_certificate_transfer_policy(
+        env: &Env,
+        issuer: Address,
+        certificate_id: CertificateId,
+        policy: TransferPolicy,
+    ) -> Result<bool, Error> {
+        transfer::set_certificate_transfer_policy(env, issuer, certificate_id, policy)
+    }
+    
+    // Set the transfer policy of the certificates of a certification type
+    pub fn set_type_transfer_policy(
+        env: &Env,
+        cert_type_id: String,
+        policy: TransferPolicy,
+    ) -> Result<bool, Error> {
+        transfer::set_type_transfer_policy(env, cert_type_id, policy)
+    }
+    
+    // Get the transfer policy that applies to a certificate
+    pub fn get_transfer_policy(
+        env: &Env,
+        certificate_id: CertificateId,
+    ) -> Result<TransferPolicy, Error> {
+        transfer::get_transfer_policy(env, certificate_id)
+    }
+    
     // === Certificate Verification ===
     
     // Verify a certificate's signature
@@ -186,10 +714,207 @@ impl CertificationContract {
         verification::verify_certificate_signature(env, certificate_id)
     }
     
+    // Verify a certificate and report why it is or is not valid
+    pub fn verify_certificate(env: &Env, certificate_id: CertificateId) -> VerificationReport {
+        verification::verify_certificate(env, certificate_id)
+    }
+    
+    // Verify the certificate a decoded QR verification payload points to
+    pub fn verify_qr_payload(env: &Env, payload: Bytes) -> Result<VerificationReport, Error> {
+        qr_payload::verify_qr_payload(env, payload)
+    }
+    
+    // Get the Merkle root committed over a certificate's metadata fields
+    pub fn get_metadata_root(env: &Env, certificate_id: CertificateId) -> Result<BytesN<32>, Error> {
+        disclosure::get_metadata_root(env, certificate_id)
+    }
+    
+    // Check metadata fields a holder disclosed, with their salts and proofs,
+    // against the certificate's commitment
+    pub fn verify_disclosed_fields(
+        env: &Env,
+        certificate_id: CertificateId,
+        fields: Vec<DisclosedField>,
+        salts: Vec<BytesN<32>>,
+        proofs: Vec<Vec<BytesN<32>>>,
+    ) -> Result<bool, Error> {
+        disclosure::verify_disclosed_fields(env, certificate_id, fields, salts, proofs)
+    }
+    
+    // Get the evidence submitted when a certificate was issued
+    pub fn get_evidence(env: &Env, certificate_id: CertificateId) -> Vec<Evidence> {
+        evidence::get_evidence(env, certificate_id)
+    }
+    
+    // Check that a document matches the evidence submitted under a name
+    pub fn verify_evidence(
+        env: &Env,
+        certificate_id: CertificateId,
+        name: String,
+        document_hash: BytesN<32>,
+    ) -> Result<bool, Error> {
+        evidence::verify_evidence(env, certificate_id, name, document_hash)
+    }
+    
     // === Certificate Revocation ===
     
-    // Revoke a certificate
-    pub fn revoke_certificate(env: &Env, certificate_id: CertificateId) -> bool {
-        access_control::revoke_certificate(env, certificate_id)
+    // Revoke a certificate with a reason, a note and an optional backdated effective date
+    pub fn revoke_certificate(
+        env: &Env,
+        issuer: Address,
+        certificate_id: CertificateId,
+        reason: RevocationReason,
+        note: String,
+        effective_date: Option<u64>,
+    ) -> Result<bool, Error> {
+        revocation::revoke_certificate(env, issuer, certificate_id, reason, note, effective_date)
+    }
+    
+    // Revoke several certificates with the same reason, note and effective date
+    pub fn batch_revoke_certificates(
+        env: &Env,
+        issuer: Address,
+        certificate_ids: Vec<CertificateId>,
+        reason: RevocationReason,
+        note: String,
+        effective_date: Option<u64>,
+        mode: BatchMode,
+    ) -> Result<Vec<BatchItemResult>, Error> {
+        batch::batch_revoke_certificates(
+            env,
+            issuer,
+            certificate_ids,
+            reason,
+            note,
+            effective_date,
+            mode,
+        )
+    }
+    
+    // Get the revocation recorded for a certificate
+    pub fn get_revocation(env: &Env, certificate_id: CertificateId) -> Result<Revocation, Error> {
+        revocation::get_revocation(env, certificate_id)
+    }
+    
+    // List revocations recorded at or after `since`, paged by registry cursor
+    pub fn list_revocations(env: &Env, since: u64, cursor: u32, limit: u32) -> RevocationPage {
+        revocation::list_revocations(env, since, cursor, limit)
+    }
+    
+    // === Certificate Amendments ===
+    
+    // Amend a certificate's metadata, keeping the previous versions
+    pub fn amend_certificate(
+        env: &Env,
+        issuer: Address,
+        certificate_id: CertificateId,
+        metadata: CertificateMetadata,
+        metadata_root: BytesN<32>,
+        note: String,
+        signing_key: BytesN<32>,
+        signature: BytesN<64>,
+    ) -> Result<u32, Error> {
+        amendment::amend_certificate(
+            env,
+            issuer,
+            certificate_id,
+            metadata,
+            metadata_root,
+            note,
+            signing_key,
+            signature,
+        )
+    }
+    
+    // Get a version of a certificate, starting from 1 for the issued content
+    pub fn get_certificate_version(
+        env: &Env,
+        certificate_id: CertificateId,
+        version: u32,
+    ) -> Result<CertificateVersion, Error> {
+        amendment::get_certificate_version(env, certificate_id, version)
+    }
+    
+    // Get every version of a certificate, oldest first
+    pub fn get_certificate_history(
+        env: &Env,
+        certificate_id: CertificateId,
+    ) -> Result<Vec<CertificateVersion>, Error> {
+        amendment::get_certificate_history(env, certificate_id)
+    }
+    
+    // === Certificate Lifecycle ===
+    
+    // Temporarily suspend a certificate, optionally lifting it automatically at `lift_at`
+    pub fn suspend_certificate(
+        env: &Env,
+        issuer: Address,
+        certificate_id: CertificateId,
+        reason: String,
+        lift_at: Option<u64>,
+    ) -> Result<bool, Error> {
+        lifecycle::suspend_certificate(env, issuer, certificate_id, reason, lift_at)
+    }
+    
+    // Lift a certificate's suspension
+    pub fn reinstate_certificate(
+        env: &Env,
+        issuer: Address,
+        certificate_id: CertificateId,
+    ) -> Result<bool, Error> {
+        lifecycle::reinstate_certificate(env, issuer, certificate_id)
+    }
+    
+    // Get the current status of a certificate
+    pub fn get_certificate_status(
+        env: &Env,
+        certificate_id: CertificateId,
+    ) -> Result<CertificateStatus, Error> {
+        lifecycle::get_certificate_status(env, certificate_id)
+    }
+    
+    // Get the most recent suspension of a certificate
+    pub fn get_suspension(env: &Env, certificate_id: CertificateId) -> Result<Suspension, Error> {
+        lifecycle::get_suspension(env, certificate_id)
+    }
+    
+    // Mark certificates past their expiration date as expired, visiting at most
+    // `limit` expiry index entries. Anyone may call it.
+    pub fn process_expirations(env: &Env, caller: Address, limit: u32) -> Result<u32, Error> {
+        expiry::process_expirations(env, caller, limit)
+    }
+    
+    // Set or remove the reward paid per certificate marked expired by `process_expirations`
+    pub fn set_expiry_reward(env: &Env, reward: Option<ExpiryReward>) -> Result<bool, Error> {
+        expiry::set_expiry_reward(env, reward)
+    }
+    
+    // Get the reward paid per certificate marked expired
+    pub fn get_expiry_reward(env: &Env) -> Option<ExpiryReward> {
+        expiry::get_expiry_reward(env)
+    }
+    
+    // === Upgrades ===
+    
+    // Replace the contract's code with an uploaded Wasm
+    pub fn upgrade(env: &Env, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        upgrade::upgrade(env, new_wasm_hash)
+    }
+    
+    // Migrate stored data to the schema version of the current code
+    pub fn migrate(env: &Env) -> Result<u32, Error> {
+        upgrade::migrate(env)
+    }
+    
+    // Get the schema version of the stored data
+    pub fn get_schema_version(env: &Env) -> u32 {
+        upgrade::get_schema_version(env)
+    }
+    
+    // === Storage Migration ===
+    
+    // Move certificates from the legacy one-byte storage slots to the typed keys
+    pub fn migrate_legacy_storage(env: &Env, start_slot: u32, limit: u32) -> Result<u32, Error> {
+        migration::migrate_legacy_storage(env, start_slot, limit)
     }
 }
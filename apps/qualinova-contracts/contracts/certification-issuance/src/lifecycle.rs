@@ -0,0 +1,142 @@
+use crate::{
+    access_control, storage, Certificate, CertificateId, CertificateStatus, Error, Suspension,
+};
+use soroban_sdk::{Address, Env, String, Symbol};
+
+// Events
+const CERTIFICATE_SUSPENDED_EVENT: &str = "certificate_suspended";
+const CERTIFICATE_REINSTATED_EVENT: &str = "certificate_reinstated";
+
+// Suspend a certificate until it is reinstated or `lift_at` is reached
+pub fn suspend_certificate(
+    env: &Env,
+    issuer: Address,
+    certificate_id: CertificateId,
+    reason: String,
+    lift_at: Option<u64>,
+) -> Result<bool, Error> {
+    // Get the certificate
+    let mut certificate = storage::get_certificate(env, &certificate_id)
+        .ok_or(Error::CertificateNotFound)?;
+    
+    // Only the issuing issuer or the admin may suspend
+    access_control::require_certificate_issuer(env, &issuer, &certificate)?;
+    
+    // Only active certificates can be suspended
+    match effective_status(env, &certificate) {
+        CertificateStatus::Active => {}
+        CertificateStatus::Suspended => return Ok(false),
+        CertificateStatus::Withdrawn => return Err(Error::CertificateWithdrawn),
+        CertificateStatus::Expired => return Err(Error::CertificateExpired),
+        CertificateStatus::Superseded => return Err(Error::CertificateSuperseded),
+    }
+    
+    // The automatic lift date must be in the future
+    let suspended_at = env.ledger().timestamp();
+    if lift_at.is_some_and(|lift_at| lift_at <= suspended_at) {
+        return Err(Error::InvalidLiftDate);
+    }
+    
+    // Update the certificate and record the suspension
+    certificate.status = CertificateStatus::Suspended;
+    storage::set_certificate(env, &certificate);
+    storage::set_suspension(
+        env,
+        &certificate_id,
+        &Suspension {
+            reason: reason.clone(),
+            suspended_by: issuer.clone(),
+            suspended_at,
+            lift_at,
+        },
+    );
+    
+    // Emit certificate suspended event
+    env.events().publish(
+        (Symbol::new(env, CERTIFICATE_SUSPENDED_EVENT),),
+        (certificate_id, issuer, reason, lift_at),
+    );
+    
+    Ok(true)
+}
+
+// Lift the suspension of a certificate
+pub fn reinstate_certificate(
+    env: &Env,
+    issuer: Address,
+    certificate_id: CertificateId,
+) -> Result<bool, Error> {
+    // Get the certificate
+    let mut certificate = storage::get_certificate(env, &certificate_id)
+        .ok_or(Error::CertificateNotFound)?;
+    
+    // Only the issuing issuer or the admin may reinstate
+    access_control::require_certificate_issuer(env, &issuer, &certificate)?;
+    
+    // Check if the certificate is still suspended
+    if effective_status(env, &certificate) != CertificateStatus::Suspended {
+        return Ok(false);
+    }
+    
+    // Update the certificate, keeping the suspension record for reference
+    certificate.status = CertificateStatus::Active;
+    storage::set_certificate(env, &certificate);
+    
+    // Emit certificate reinstated event
+    env.events().publish(
+        (Symbol::new(env, CERTIFICATE_REINSTATED_EVENT),),
+        (certificate_id, issuer),
+    );
+    
+    Ok(true)
+}
+
+// Get the current status of a certificate
+pub fn get_certificate_status(
+    env: &Env,
+    certificate_id: CertificateId,
+) -> Result<CertificateStatus, Error> {
+    let certificate = storage::get_certificate(env, &certificate_id)
+        .ok_or(Error::CertificateNotFound)?;
+    
+    Ok(effective_status(env, &certificate))
+}
+
+// Get the most recent suspension of a certificate, even if it has been lifted
+pub fn get_suspension(env: &Env, certificate_id: CertificateId) -> Result<Suspension, Error> {
+    storage::get_suspension(env, &certificate_id).ok_or(Error::SuspensionNotFound)
+}
+
+// Work out the status of a certificate at the current ledger time.
+//
+// The stored status only changes on explicit actions, so suspensions that
+// reached their lift date and certificates past their expiration date are
+// resolved here.
+pub fn effective_status(env: &Env, certificate: &Certificate) -> CertificateStatus {
+    let now = env.ledger().timestamp();
+    
+    let status = match certificate.status {
+        CertificateStatus::Suspended => match storage::get_suspension(env, &certificate.id) {
+            Some(Suspension { lift_at: Some(lift_at), .. }) if lift_at <= now => {
+                CertificateStatus::Active
+            }
+            _ => CertificateStatus::Suspended,
+        },
+        status => status,
+    };
+    
+    // Check if the certificate is expired; expiry also ends a suspension
+    if matches!(status, CertificateStatus::Active | CertificateStatus::Suspended)
+        && certificate.expiration_date.is_some_and(|expiration| now > expiration)
+    {
+        return CertificateStatus::Expired;
+    }
+    
+    status
+}
+
+// Return the certificate with its status resolved at the current ledger time
+pub fn with_effective_status(env: &Env, mut certificate: Certificate) -> Certificate {
+    certificate.status = effective_status(env, &certificate);
+    certificate
+}
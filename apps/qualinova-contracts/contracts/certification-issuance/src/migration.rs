@@ -0,0 +1,143 @@
+use crate::{
+    access_control, expiry, index, storage, Certificate, CertificateId, CertificateMetadata,
+    CertificateStatus, Error,
+};
+use alloc::format;
+use core::cmp;
+use soroban_sdk::{contracttype, Address, Bytes, BytesN, Env, String, Symbol};
+
+// Legacy storage keys were built from the first byte of a hash, so there are only 256 slots
+pub const LEGACY_SLOT_COUNT: u32 = 256;
+
+// Legacy storage key prefixes
+const LEGACY_CERT_PREFIX: &str = "cert";
+const LEGACY_OWNER_CERTS_PREFIX: &str = "owner_certs";
+const LEGACY_ISSUER_CERTS_PREFIX: &str = "issuer_certs";
+
+// Instance storage key of the number of legacy slots migrated so far, counted
+// from slot 0
+const LEGACY_SLOTS_MIGRATED_KEY: &str = "legacy_slots_migrated";
+
+// Events
+const LEGACY_STORAGE_MIGRATED_EVENT: &str = "legacy_storage_migrated";
+
+// Certificate layout written under the legacy keys
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LegacyCertificate {
+    pub id: CertificateId,
+    pub owner: Address,
+    pub issuer: Address,
+    pub metadata: CertificateMetadata,
+    pub issuance_date: u64,
+    pub expiration_date: Option<u64>,
+    pub revoked: bool,
+    pub signature: Bytes,
+}
+
+impl LegacyCertificate {
+    // Convert to the current layout. Legacy signatures were never checked, so the
+    // certificate gets the all-zero signing key, which can never be registered, and
+    // `verify_certificate` reports it as `BadSignature` until it is reissued.
+    // Revoked certificates become withdrawn; the legacy layout kept no reason or
+    // date, so they get no revocation registry entry. Legacy certificates predate
+    // certification types and get an empty type ID.
+    fn upgrade(self, env: &Env) -> Certificate {
+        let mut signature = [0u8; 64];
+        if self.signature.len() == 64 {
+            self.signature.copy_into_slice(&mut signature);
+        }
+        
+        Certificate {
+            id: self.id,
+            owner: self.owner,
+            issuer: self.issuer,
+            cert_type_id: String::from_str(env, ""),
+            metadata: self.metadata,
+            issuance_date: self.issuance_date,
+            expiration_date: self.expiration_date,
+            status: if self.revoked {
+                CertificateStatus::Withdrawn
+            } else {
+                CertificateStatus::Active
+            },
+            signature: BytesN::from_array(env, &signature),
+            signing_key: BytesN::from_array(env, &[0u8; 32]),
+        }
+    }
+}
+
+// Move certificates stored under the legacy one-byte keys to the typed keys.
+//
+// Certificates that were overwritten by a slot collision are already gone, so the
+// owner and issuer indexes are rebuilt from the surviving certificates rather than
+// copied from the (equally collision-prone) legacy index lists.
+pub fn migrate_legacy_storage(env: &Env, start_slot: u32, limit: u32) -> Result<u32, Error> {
+    // Verify the caller is the admin
+    access_control::require_admin(env)?;
+    
+    let start = cmp::min(start_slot, LEGACY_SLOT_COUNT);
+    let end = cmp::min(start.saturating_add(limit), LEGACY_SLOT_COUNT);
+    let mut migrated = 0;
+    
+    for slot in start..end {
+        let certificate_key = legacy_key(env, LEGACY_CERT_PREFIX, slot, "");
+        let legacy_certificate: Option<LegacyCertificate> =
+            env.storage().persistent().get(&certificate_key);
+        
+        if let Some(legacy_certificate) = legacy_certificate {
+            let certificate = legacy_certificate.upgrade(env);
+            if !storage::has_certificate(env, &certificate.id) {
+                storage::set_certificate(env, &certificate);
+                index::index_certificate(env, &certificate);
+                expiry::index_expiration(env, &certificate);
+                migrated += 1;
+            }
+            env.storage().persistent().remove(&certificate_key);
+        }
+        
+        // Drop the legacy index lists that shared this slot
+        env.storage()
+            .persistent()
+            .remove(&legacy_key(env, LEGACY_OWNER_CERTS_PREFIX, slot, "_"));
+        env.storage()
+            .persistent()
+            .remove(&legacy_key(env, LEGACY_ISSUER_CERTS_PREFIX, slot, "_"));
+    }
+    
+    // Record how far the slots from 0 on are covered, so `migrate` can tell when
+    // every slot is done
+    let covered = get_legacy_slots_migrated(env);
+    if start <= covered && end > covered {
+        env.storage()
+            .instance()
+            .set(&Symbol::new(env, LEGACY_SLOTS_MIGRATED_KEY), &end);
+    }
+    
+    // Emit legacy storage migrated event
+    env.events().publish(
+        (Symbol::new(env, LEGACY_STORAGE_MIGRATED_EVENT),),
+        (start, end, migrated),
+    );
+    
+    Ok(migrated)
+}
+
+// Check whether every legacy slot has been migrated
+pub fn is_legacy_storage_migrated(env: &Env) -> bool {
+    get_legacy_slots_migrated(env) >= LEGACY_SLOT_COUNT
+}
+
+// Get the number of legacy slots migrated so far, counted from slot 0
+fn get_legacy_slots_migrated(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&Symbol::new(env, LEGACY_SLOTS_MIGRATED_KEY))
+        .unwrap_or(0)
+}
+
+// Rebuild a legacy key such as "cert3f" or "owner_certs_3f"
+fn legacy_key(env: &Env, prefix: &str, slot: u32, separator: &str) -> Symbol {
+    let key = format!("{}{}{:x}", prefix, separator, slot);
+    Symbol::new(env, &key)
+}
@@ -0,0 +1,160 @@
+use crate::{
+    lifecycle, storage, transfer, CertificateId, CertificateStatus, Error, TransferOffer,
+    TransferPolicy,
+};
+use soroban_sdk::{Address, Env, Symbol, Vec};
+
+// Events
+const TRANSFER_OFFERED_EVENT: &str = "transfer_offered";
+const TRANSFER_ACCEPTED_EVENT: &str = "transfer_accepted";
+const TRANSFER_CANCELLED_EVENT: &str = "transfer_cancelled";
+
+// Offer a certificate to a new owner. A new offer replaces any pending one.
+pub fn offer_transfer(
+    env: &Env,
+    certificate_id: CertificateId,
+    to: Address,
+    expires_at: u64,
+) -> Result<bool, Error> {
+    // Get the certificate
+    let certificate = storage::get_certificate(env, &certificate_id)
+        .ok_or(Error::CertificateNotFound)?;
+    
+    // Require authorization from the current owner
+    certificate.owner.require_auth();
+    
+    // Only active certificates can change hands
+    if lifecycle::effective_status(env, &certificate) != CertificateStatus::Active {
+        return Ok(false);
+    }
+    
+    // Fail early if the certificate can never be transferred
+    if transfer::effective_transfer_policy(env, &certificate) == TransferPolicy::NonTransferable {
+        return Err(Error::NotTransferable);
+    }
+    
+    // Check the recipient and the expiry
+    if to == certificate.owner {
+        return Err(Error::InvalidInput);
+    }
+    let offered_at = env.ledger().timestamp();
+    if expires_at <= offered_at {
+        return Err(Error::InvalidExpirationDate);
+    }
+    
+    // Replace any pending offer
+    clear_offer(env, &certificate_id);
+    storage::set_transfer_offer(
+        env,
+        &TransferOffer {
+            certificate_id: certificate_id.clone(),
+            from: certificate.owner.clone(),
+            to: to.clone(),
+            offered_at,
+            expires_at,
+        },
+    );
+    
+    // Emit transfer offered event
+    env.events().publish(
+        (Symbol::new(env, TRANSFER_OFFERED_EVENT),),
+        (certificate_id, certificate.owner, to, expires_at),
+    );
+    
+    Ok(true)
+}
+
+// Accept a pending offer and take ownership of the certificate
+pub fn accept_transfer(
+    env: &Env,
+    recipient: Address,
+    certificate_id: CertificateId,
+) -> Result<bool, Error> {
+    // Require authorization from the recipient
+    recipient.require_auth();
+    
+    // Get the offer and the certificate
+    let offer = storage::get_transfer_offer(env, &certificate_id).ok_or(Error::OfferNotFound)?;
+    let certificate = storage::get_certificate(env, &certificate_id)
+        .ok_or(Error::CertificateNotFound)?;
+    
+    // Only the recipient can accept, and only before the offer expires
+    if offer.to != recipient {
+        return Err(Error::NotOfferParty);
+    }
+    if env.ledger().timestamp() >= offer.expires_at {
+        return Err(Error::OfferExpired);
+    }
+    
+    // Only active certificates can change hands
+    if lifecycle::effective_status(env, &certificate) != CertificateStatus::Active {
+        return Ok(false);
+    }
+    
+    // Check the transfer policy as it stands now
+    transfer::require_transfer_allowed(env, &certificate)?;
+    
+    // Move the certificate, which also clears the offer
+    transfer::move_certificate(env, certificate, recipient.clone());
+    
+    // Emit transfer accepted event
+    env.events().publish(
+        (Symbol::new(env, TRANSFER_ACCEPTED_EVENT),),
+        (certificate_id, offer.from, recipient),
+    );
+    
+    Ok(true)
+}
+
+// Cancel a pending offer, either as the owner who made it or as the recipient
+// declining it
+pub fn cancel_transfer(env: &Env, caller: Address, certificate_id: CertificateId) -> Result<bool, Error> {
+    // Require authorization from the caller
+    caller.require_auth();
+    
+    // Get the offer
+    let offer = storage::get_transfer_offer(env, &certificate_id).ok_or(Error::OfferNotFound)?;
+    
+    // Only the parties to the offer can cancel it
+    if caller != offer.from && caller != offer.to {
+        return Err(Error::NotOfferParty);
+    }
+    
+    storage::remove_transfer_offer(env, &offer);
+    
+    // Emit transfer cancelled event
+    env.events().publish(
+        (Symbol::new(env, TRANSFER_CANCELLED_EVENT),),
+        (certificate_id, offer.from, offer.to, caller),
+    );
+    
+    Ok(true)
+}
+
+// Get the pending offer for a certificate
+pub fn get_transfer_offer(env: &Env, certificate_id: CertificateId) -> Result<TransferOffer, Error> {
+    storage::get_transfer_offer(env, &certificate_id).ok_or(Error::OfferNotFound)
+}
+
+// Get the unexpired offers made by or to an address
+pub fn get_pending_offers(env: &Env, address: Address) -> Vec<TransferOffer> {
+    let now = env.ledger().timestamp();
+    let mut offers = Vec::new(env);
+    
+    for cert_id in storage::get_pending_offers(env, &address).iter() {
+        if let Some(offer) = storage::get_transfer_offer(env, &cert_id) {
+            if now < offer.expires_at {
+                offers.push_back(offer);
+            }
+        }
+    }
+    
+    offers
+}
+
+// Drop the pending offer for a certificate, if any
+pub fn clear_offer(env: &Env, certificate_id: &CertificateId) {
+    if let Some(offer) = storage::get_transfer_offer(env, certificate_id) {
+        storage::remove_transfer_offer(env, &offer);
+    }
+}
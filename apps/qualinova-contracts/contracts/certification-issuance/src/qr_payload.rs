@@ -0,0 +1,51 @@
+use crate::{verification, CertificateId, Error, VerificationReport};
+use soroban_sdk::xdr::ToXdr;
+use soroban_sdk::{Bytes, BytesN, Env};
+
+// Version of the payload layout
+const PAYLOAD_VERSION: u32 = 1;
+
+// Length of a version 1 payload: the version, the certificate ID, the contract ID,
+// the network ID and a 4-byte checksum
+const PAYLOAD_LEN: u32 = 101;
+
+// Offset of the checksum, the start of the SHA-256 of the bytes before it
+const CHECKSUM_OFFSET: u32 = 97;
+
+// Verify the certificate a decoded QR payload points to. The payload must have
+// been made for this contract on this network.
+pub fn verify_qr_payload(env: &Env, payload: Bytes) -> Result<VerificationReport, Error> {
+    match payload.first() {
+        None => return Err(Error::InvalidPayload),
+        Some(version) if version as u32 != PAYLOAD_VERSION => {
+            return Err(Error::UnsupportedPayloadVersion)
+        }
+        Some(_) => {}
+    }
+    if payload.len() != PAYLOAD_LEN {
+        return Err(Error::InvalidPayload);
+    }
+    
+    let hash: Bytes = env.crypto().sha256(&payload.slice(..CHECKSUM_OFFSET)).to_bytes().into();
+    if hash.slice(..PAYLOAD_LEN - CHECKSUM_OFFSET) != payload.slice(CHECKSUM_OFFSET..) {
+        return Err(Error::InvalidPayload);
+    }
+    
+    if payload.slice(33..65) != current_contract_id(env) {
+        return Err(Error::PayloadContractMismatch);
+    }
+    
+    let network_id: Bytes = env.ledger().network_id().into();
+    if payload.slice(65..97) != network_id {
+        return Err(Error::PayloadNetworkMismatch);
+    }
+    
+    let certificate_id = CertificateId(BytesN::try_from(payload.slice(1..33)).unwrap());
+    Ok(verification::verify_certificate(env, certificate_id))
+}
+
+// Get the 32-byte ID of this contract, which follows the ScVal and ScAddress
+// discriminants in its address's XDR
+fn current_contract_id(env: &Env) -> Bytes {
+    env.current_contract_address().to_xdr(env).slice(8..40)
+}
@@ -1,114 +1,227 @@
-use crate::{issuance, Certificate, CertificateId};
-use alloc::format;
+use crate::storage::IndexScope;
+use crate::{
+    lifecycle, storage, Certificate, CertificateFilter, CertificateId, CertificateOrder,
+    CertificatePage, Error,
+};
 use core::cmp;
-use soroban_sdk::{Address, Bytes, Env, Symbol, Vec};
-use soroban_sdk::xdr::ToXdr;
+use soroban_sdk::{Address, Env, Vec};
 
-// Storage keys format
-const ISSUER_CERTS_PREFIX: &str = "issuer_certs";
+// Most index slots a filtered listing reads in one call. Statuses and issuance
+// dates are checked on each certificate read, so this bounds the reads of a
+// call however few certificates match.
+pub const MAX_FILTER_SLOTS: u32 = 100;
 
-// Get a certificate by its ID
-pub fn get_certificate(env: &Env, certificate_id: CertificateId) -> Certificate {
-    let cert_id_bytes = env.crypto().sha256(&Bytes::from_slice(env, &certificate_id.0.to_array()));
-    let certificate_key = get_certificate_key(env, &cert_id_bytes);
-    
-    env.storage()
-        .persistent()
-        .get(&certificate_key)
-        .unwrap_or_else(|| panic!("Certificate not found"))
+// Most entries a plain listing returns in one call, so a large `limit` cannot
+// push a call past the read limits of a transaction
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+// Get a certificate by its ID, with its status resolved at the current ledger time
+pub fn get_certificate(env: &Env, certificate_id: CertificateId) -> Result<Certificate, Error> {
+    storage::get_certificate(env, &certificate_id)
+        .map(|certificate| lifecycle::with_effective_status(env, certificate))
+        .ok_or(Error::CertificateNotFound)
 }
 
-// List certificates by owner with pagination
+// List an owner's certificates, following a cursor over the slots of the owner's
+// index. Slots of certificates transferred away, or whose record is missing, are
+// skipped, so a page can hold fewer than `limit` certificates.
 pub fn list_certificates_by_owner(
     env: &Env,
     owner: Address,
-    start_index: u32,
+    cursor: u32,
     limit: u32,
-) -> Vec<Certificate> {
-    // Get all certificate IDs for the owner
-    let cert_ids = issuance::get_owner_certificates(env, &owner);
-    
-    // Apply pagination
-    let start = if start_index >= cert_ids.len() {
-        return Vec::new(env);
-    } else {
-        start_index as u32
-    };
+) -> CertificatePage {
+    let limit = cmp::min(limit, MAX_PAGE_SIZE);
+    let len = storage::get_index(env, &IndexScope::Owner(owner.clone())).len;
+    let cert_ids = storage::get_index_slots(env, &IndexScope::Owner(owner.clone()), cursor, limit);
     
-    let end = cmp::min(start + limit, cert_ids.len());
-    
-    // Fetch certificates
+    // Fetch the certificates the owner still holds
     let mut certificates = Vec::new(env);
-    for i in start..end {
-        let cert_id = cert_ids.get(i).unwrap();
-        let certificate = get_certificate(env, cert_id);
-        certificates.push_back(certificate);
+    for cert_id in cert_ids.iter() {
+        if let Some(certificate) = storage::get_certificate(env, &cert_id) {
+            if certificate.owner == owner {
+                certificates.push_back(lifecycle::with_effective_status(env, certificate));
+            }
+        }
     }
     
-    certificates
+    certificate_page(certificates, cursor, cert_ids.len(), len)
 }
 
-// List certificates by issuer with pagination
+// List an issuer's certificates, following a cursor over the slots of the
+// issuer's index. Slots whose certificate record is missing are skipped.
 pub fn list_certificates_by_issuer(
     env: &Env,
     issuer: Address,
-    start_index: u32,
+    cursor: u32,
     limit: u32,
-) -> Vec<Certificate> {
-    // Get all certificate IDs for the issuer
-    let cert_ids = get_issuer_certificates(env, &issuer);
-    
-    // Apply pagination
-    let start = if start_index >= cert_ids.len() {
-        return Vec::new(env);
-    } else {
-        start_index as u32
-    };
-    
-    let end = cmp::min(start + limit, cert_ids.len());
+) -> CertificatePage {
+    let limit = cmp::min(limit, MAX_PAGE_SIZE);
+    let len = storage::get_index(env, &IndexScope::Issuer(issuer.clone())).len;
+    let cert_ids = storage::get_index_slots(env, &IndexScope::Issuer(issuer), cursor, limit);
     
     // Fetch certificates
     let mut certificates = Vec::new(env);
-    for i in start..end {
-        let cert_id = cert_ids.get(i).unwrap();
-        let certificate = get_certificate(env, cert_id);
-        certificates.push_back(certificate);
+    for cert_id in cert_ids.iter() {
+        if let Some(certificate) = storage::get_certificate(env, &cert_id) {
+            certificates.push_back(lifecycle::with_effective_status(env, certificate));
+        }
     }
     
-    certificates
+    certificate_page(certificates, cursor, cert_ids.len(), len)
 }
 
-// Get all certificates for an issuer
-fn get_issuer_certificates(env: &Env, issuer: &Address) -> Vec<CertificateId> {
-    let issuer_key = get_issuer_key(env, issuer);
+// List an owner's certificates matching a filter. A certification type or
+// achievement type selects the secondary index that is walked; the other
+// conditions are checked on each certificate read, so a page can come back
+// short or empty while `next_cursor` is still set.
+pub fn filter_certificates_by_owner(
+    env: &Env,
+    owner: Address,
+    filter: CertificateFilter,
+    cursor: Option<u32>,
+    limit: u32,
+) -> Result<CertificatePage, Error> {
+    let scope = match (&filter.cert_type_id, &filter.achievement_type) {
+        (Some(cert_type_id), _) => IndexScope::OwnerType(owner.clone(), cert_type_id.clone()),
+        (None, Some(achievement_type)) => {
+            IndexScope::OwnerAchievement(owner.clone(), achievement_type.clone())
+        }
+        (None, None) => IndexScope::Owner(owner.clone()),
+    };
     
-    env.storage()
-        .persistent()
-        .get(&issuer_key)
-        .unwrap_or_else(|| Vec::new(env))
+    // Slots of certificates transferred away are skipped
+    filter_certificates(env, &scope, &filter, cursor, limit, |certificate| {
+        certificate.owner == owner
+    })
+}
+
+// List an issuer's certificates matching a filter, walking the same way as for
+// an owner
+pub fn filter_certificates_by_issuer(
+    env: &Env,
+    issuer: Address,
+    filter: CertificateFilter,
+    cursor: Option<u32>,
+    limit: u32,
+) -> Result<CertificatePage, Error> {
+    let scope = match (&filter.cert_type_id, &filter.achievement_type) {
+        (Some(cert_type_id), _) => IndexScope::IssuerType(issuer, cert_type_id.clone()),
+        (None, Some(achievement_type)) => {
+            IndexScope::IssuerAchievement(issuer, achievement_type.clone())
+        }
+        (None, None) => IndexScope::Issuer(issuer),
+    };
+    
+    filter_certificates(env, &scope, &filter, cursor, limit, |_| true)
 }
 
 // Count certificates by owner
 pub fn count_certificates_by_owner(env: &Env, owner: &Address) -> u32 {
-    issuance::get_owner_certificates(env, owner).len()
+    storage::get_index(env, &IndexScope::Owner(owner.clone())).count
 }
 
 // Count certificates by issuer
 pub fn count_certificates_by_issuer(env: &Env, issuer: &Address) -> u32 {
-    get_issuer_certificates(env, issuer).len()
+    storage::get_index(env, &IndexScope::Issuer(issuer.clone())).count
+}
+
+// Build a page of certificates read from `read` index slots starting at `cursor`
+fn certificate_page(
+    certificates: Vec<Certificate>,
+    cursor: u32,
+    read: u32,
+    len: u32,
+) -> CertificatePage {
+    let end = cursor.saturating_add(read);
+    
+    CertificatePage {
+        certificates,
+        next_cursor: if end < len { Some(end) } else { None },
+    }
 }
 
-// Helper function to get a certificate key from a hash
-fn get_certificate_key(env: &Env, hash: &soroban_sdk::crypto::Hash<32>) -> Symbol {
-    // Create a simple key with the hash - avoid using colons
-    let key = format!("cert{:x}", hash.to_array()[0]);
-    Symbol::new(env, &key)
+// Walk up to `limit` slots of an index, and never more than `MAX_FILTER_SLOTS`,
+// in the filter's order, starting at the slot `cursor` or at the start of the
+// order, and keep the certificates that match. The next cursor is the slot after
+// the last one read.
+fn filter_certificates(
+    env: &Env,
+    scope: &IndexScope,
+    filter: &CertificateFilter,
+    cursor: Option<u32>,
+    limit: u32,
+    listed: impl Fn(&Certificate) -> bool,
+) -> Result<CertificatePage, Error> {
+    if let (Some(from), Some(to)) = (filter.issued_from, filter.issued_to) {
+        if from > to {
+            return Err(Error::InvalidInput);
+        }
+    }
+    
+    // Slots to read, in ascending order
+    let limit = cmp::min(limit, MAX_FILTER_SLOTS);
+    let len = storage::get_index(env, scope).len;
+    let (start, end) = match filter.order {
+        CertificateOrder::OldestFirst => {
+            let start = cursor.unwrap_or(0);
+            (start, cmp::min(start.saturating_add(limit), len))
+        }
+        CertificateOrder::NewestFirst => {
+            let end = cursor.map_or(len, |cursor| cmp::min(cursor.saturating_add(1), len));
+            (end.saturating_sub(limit), end)
+        }
+    };
+    let cert_ids = if start < end {
+        storage::get_index_slots(env, scope, start, end - start)
+    } else {
+        Vec::new(env)
+    };
+    
+    // Fetch the matching certificates in the filter's order
+    let mut certificates = Vec::new(env);
+    for i in 0..cert_ids.len() {
+        let slot = match filter.order {
+            CertificateOrder::OldestFirst => i,
+            CertificateOrder::NewestFirst => cert_ids.len() - 1 - i,
+        };
+        let Some(certificate) = storage::get_certificate(env, &cert_ids.get(slot).unwrap()) else {
+            continue;
+        };
+        if listed(&certificate) && matches_filter(env, filter, &certificate) {
+            certificates.push_back(lifecycle::with_effective_status(env, certificate));
+        }
+    }
+    
+    let next_cursor = match filter.order {
+        CertificateOrder::OldestFirst if end < len => Some(end),
+        CertificateOrder::NewestFirst if start > 0 && start < end => Some(start - 1),
+        _ => None,
+    };
+    
+    Ok(CertificatePage {
+        certificates,
+        next_cursor,
+    })
 }
 
-// Helper function to get an issuer key
-fn get_issuer_key(env: &Env, issuer: &Address) -> Symbol {
-    let issuer_hash = env.crypto().sha256(&issuer.clone().to_xdr(env));
-    // Avoid using colons in Symbol keys
-    let key = format!("{}_{:x}", ISSUER_CERTS_PREFIX, issuer_hash.to_array()[0]);
-    Symbol::new(env, &key)
+// Check whether a certificate matches every condition of a filter
+fn matches_filter(env: &Env, filter: &CertificateFilter, certificate: &Certificate) -> bool {
+    filter
+        .cert_type_id
+        .as_ref()
+        .is_none_or(|cert_type_id| *cert_type_id == certificate.cert_type_id)
+        && filter.achievement_type.as_ref().is_none_or(|achievement_type| {
+            *achievement_type == certificate.metadata.achievement_type
+        })
+        && filter
+            .issued_from
+            .is_none_or(|from| certificate.issuance_date >= from)
+        && filter
+            .issued_to
+            .is_none_or(|to| certificate.issuance_date <= to)
+        && (filter.statuses.is_empty()
+            || filter
+                .statuses
+                .contains(lifecycle::effective_status(env, certificate)))
 }
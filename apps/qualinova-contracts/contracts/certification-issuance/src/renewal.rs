@@ -0,0 +1,168 @@
+use crate::{
+    access_control, amendment, certification_types, entities, expiry, index,
+    issuance, lifecycle, storage, verification, Certificate, CertificateId, CertificateStatus,
+    Error,
+};
+use soroban_sdk::{Bytes, BytesN, Env, Symbol, Vec};
+
+// Events
+const CERTIFICATE_RENEWED_EVENT: &str = "certificate_renewed";
+
+// Renew a certificate.
+//
+// The successor keeps the owner, issuer, type, metadata, metadata root, evidence
+// and transfer policy of the original, gets a new expiration date and a fresh
+// signature from the issuer, and is linked to the original, which is marked superseded. As at issuance, the type
+// must still be active, and without a new expiration date the successor expires
+// after the type's validity period; the signature covers the date as submitted.
+pub fn renew_certificate(
+    env: &Env,
+    certificate_id: CertificateId,
+    new_expiration: Option<u64>,
+    signing_key: BytesN<32>,
+    signature: BytesN<64>,
+) -> Result<CertificateId, Error> {
+    // Get the certificate
+    let mut original = storage::get_certificate(env, &certificate_id)
+        .ok_or(Error::CertificateNotFound)?;
+    
+    // The issuer of the original must still be an authorized issuer
+    access_control::require_issuer(env, &original.issuer)?;
+    
+    // The type must still be active and the issuer accredited for it
+    let cert_type = issuance::get_issuable_certification_type(
+        env,
+        &original.issuer,
+        &original.cert_type_id,
+    )?;
+    
+    // Only active or expired certificates can be renewed
+    match lifecycle::effective_status(env, &original) {
+        CertificateStatus::Active | CertificateStatus::Expired => {}
+        CertificateStatus::Suspended => return Err(Error::CertificateSuspended),
+        CertificateStatus::Withdrawn => return Err(Error::CertificateWithdrawn),
+        CertificateStatus::Superseded => return Err(Error::CertificateSuperseded),
+    }
+    
+    // The new expiration date must be in the future
+    let issuance_date = env.ledger().timestamp();
+    if new_expiration.is_some_and(|expiration| expiration <= issuance_date) {
+        return Err(Error::InvalidExpirationDate);
+    }
+    let resolved_expiration = match new_expiration {
+        Some(expiration) => Some(expiration),
+        None => certification_types::default_expiration(&cert_type, issuance_date)?,
+    };
+    
+    // Generate a unique certificate ID
+    let owner = original.owner.clone();
+    let issuer = original.issuer.clone();
+    let cert_id = generate_successor_id(env, &original);
+    if storage::has_certificate(env, &cert_id) {
+        return Err(Error::CertificateAlreadyExists);
+    }
+    
+    // Create the successor, link it and check the issuer's signature over it
+    let successor = Certificate {
+        id: cert_id.clone(),
+        owner: owner.clone(),
+        issuer: issuer.clone(),
+        cert_type_id: original.cert_type_id.clone(),
+        metadata: original.metadata.clone(),
+        issuance_date,
+        expiration_date: resolved_expiration,
+        status: CertificateStatus::Active,
+        signature,
+        signing_key: issuance::check_signing_key(env, &issuer, signing_key)?,
+    };
+    let entity_registry = entities::owner_entity_registry(env, &owner)?;
+    storage::link_renewal(env, &certificate_id, &cert_id);
+    let metadata_root = storage::get_metadata_root(env, &certificate_id);
+    let version =
+        amendment::issued_version(env, &successor, metadata_root.as_ref(), new_expiration);
+    verification::check_version_signature(env, &version)?;
+    if resolved_expiration != new_expiration {
+        storage::add_certificate_version(env, &cert_id, &version);
+    }
+    
+    // Store the successor, carrying over the original's metadata root, evidence
+    // and transfer policy
+    storage::set_certificate(env, &successor);
+    if let Some(root) = metadata_root {
+        storage::set_metadata_root(env, &cert_id, &root);
+    }
+    storage::set_evidence(env, &cert_id, &storage::get_evidence(env, &certificate_id));
+    if let Some(policy) = storage::get_certificate_transfer_policy(env, &certificate_id) {
+        storage::set_certificate_transfer_policy(env, &cert_id, policy);
+    }
+    
+    // Update certificate counts
+    storage::increment_certificate_count(env);
+    
+    // Add to the owner's and issuer's certificate indexes and the expiry index
+    index::index_certificate(env, &successor);
+    expiry::index_expiration(env, &successor);
+    
+    // Record the successor with the owner's entity
+    if let Some(registry) = entity_registry {
+        entities::record_certification(env, &registry, &owner, &cert_id)?;
+    }
+    
+    // Mark the original as superseded
+    original.status = CertificateStatus::Superseded;
+    storage::set_certificate(env, &original);
+    
+    // Emit certificate renewed event
+    env.events().publish(
+        (Symbol::new(env, CERTIFICATE_RENEWED_EVENT),),
+        (certificate_id, cert_id.clone(), owner, issuer),
+    );
+    
+    Ok(cert_id)
+}
+
+// Generate the ID of a renewal. The renewed certificate's ID is mixed in, so a
+// renewal never collides with the certificate it replaces, even in the same ledger.
+fn generate_successor_id(env: &Env, original: &Certificate) -> CertificateId {
+    let base_id =
+        issuance::generate_certificate_id(env, &original.owner, &original.issuer, &original.metadata);
+    
+    let mut data = Bytes::from_array(env, &base_id.0.to_array());
+    data.append(&Bytes::from_array(env, &original.id.0.to_array()));
+    
+    CertificateId(env.crypto().sha256(&data).into())
+}
+
+// Get the certificate a renewal replaced
+pub fn get_predecessor(env: &Env, certificate_id: CertificateId) -> Option<CertificateId> {
+    storage::get_predecessor(env, &certificate_id)
+}
+
+// Get the certificate that renewed this one
+pub fn get_successor(env: &Env, certificate_id: CertificateId) -> Option<CertificateId> {
+    storage::get_successor(env, &certificate_id)
+}
+
+// Get the renewal chain a certificate belongs to, from the first issuance to
+// the latest renewal
+pub fn get_renewal_chain(env: &Env, certificate_id: CertificateId) -> Result<Vec<CertificateId>, Error> {
+    if !storage::has_certificate(env, &certificate_id) {
+        return Err(Error::CertificateNotFound);
+    }
+    
+    // Walk back to the first issuance
+    let mut first = certificate_id;
+    while let Some(predecessor) = storage::get_predecessor(env, &first) {
+        first = predecessor;
+    }
+    
+    // Walk forward to the latest renewal
+    let mut chain = Vec::new(env);
+    let mut current = Some(first);
+    while let Some(cert_id) = current {
+        current = storage::get_successor(env, &cert_id);
+        chain.push_back(cert_id);
+    }
+    
+    Ok(chain)
+}
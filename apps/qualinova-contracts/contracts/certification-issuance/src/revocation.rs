@@ -0,0 +1,110 @@
+use crate::{
+    access_control, query, storage, CertificateId, CertificateStatus, Error, Revocation, RevocationPage,
+    RevocationReason,
+};
+use core::cmp;
+use soroban_sdk::{Address, Env, String, Symbol, Vec};
+
+// Events
+const CERTIFICATE_REVOKED_EVENT: &str = "certificate_revoked";
+
+// Revoke a certificate and record the revocation in the registry
+pub fn revoke_certificate(
+    env: &Env,
+    issuer: Address,
+    certificate_id: CertificateId,
+    reason: RevocationReason,
+    note: String,
+    effective_date: Option<u64>,
+) -> Result<bool, Error> {
+    // Get the certificate
+    let mut certificate = storage::get_certificate(env, &certificate_id)
+        .ok_or(Error::CertificateNotFound)?;
+    
+    // Only the issuing issuer or the admin may revoke
+    access_control::require_certificate_issuer(env, &issuer, &certificate)?;
+    
+    // Check if already withdrawn
+    if certificate.status == CertificateStatus::Withdrawn {
+        return Ok(false);
+    }
+    
+    // A revocation may be backdated, but never take effect in the future
+    let recorded_at = env.ledger().timestamp();
+    let effective_date = effective_date.unwrap_or(recorded_at);
+    if effective_date > recorded_at {
+        return Err(Error::InvalidEffectiveDate);
+    }
+    
+    // Withdraw the certificate, ending any suspension
+    certificate.status = CertificateStatus::Withdrawn;
+    storage::set_certificate(env, &certificate);
+    
+    // Record the revocation
+    let sequence = storage::add_revocation(
+        env,
+        &Revocation {
+            certificate_id: certificate_id.clone(),
+            reason,
+            note,
+            revoked_by: issuer.clone(),
+            effective_date,
+            recorded_at,
+        },
+    );
+    
+    // Emit certificate revoked event
+    env.events().publish(
+        (Symbol::new(env, CERTIFICATE_REVOKED_EVENT),),
+        (certificate_id, certificate.issuer, issuer, reason, sequence),
+    );
+    
+    Ok(true)
+}
+
+// Get the revocation recorded for a certificate
+pub fn get_revocation(env: &Env, certificate_id: CertificateId) -> Result<Revocation, Error> {
+    storage::get_certificate_revocation(env, &certificate_id)
+        .and_then(|sequence| storage::get_revocation(env, sequence))
+        .ok_or(Error::RevocationNotFound)
+}
+
+// List revocations recorded at or after `since`, starting from the registry
+// position `cursor`. Pass the returned `next_cursor` to fetch the next page.
+pub fn list_revocations(env: &Env, since: u64, cursor: u32, limit: u32) -> RevocationPage {
+    let count = storage::get_revocation_count(env);
+    
+    // Revocations are appended in ledger order, so the first one recorded at
+    // or after `since` can be found with a binary search
+    let start = cmp::max(cursor, first_recorded_since(env, since, count));
+    let limit = cmp::min(limit, query::MAX_PAGE_SIZE);
+    let end = cmp::min(start.saturating_add(limit), count);
+    
+    // Fetch revocations
+    let mut revocations = Vec::new(env);
+    for sequence in start..end {
+        revocations.push_back(storage::get_revocation(env, sequence).unwrap());
+    }
+    
+    RevocationPage {
+        revocations,
+        next_cursor: if end < count { Some(end) } else { None },
+    }
+}
+
+// Find the sequence number of the first revocation recorded at or after `since`
+fn first_recorded_since(env: &Env, since: u64, count: u32) -> u32 {
+    let mut low = 0;
+    let mut high = count;
+    
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if storage::get_revocation(env, mid).unwrap().recorded_at < since {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+    
+    low
+}
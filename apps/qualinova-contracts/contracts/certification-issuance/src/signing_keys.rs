@@ -0,0 +1,114 @@
+use crate::{access_control, storage, Error, SigningKey};
+use soroban_sdk::{Address, BytesN, Env, Symbol, Vec};
+
+// Events
+const SIGNING_KEY_REGISTERED_EVENT: &str = "signing_key_registered";
+const SIGNING_KEY_REVOKED_EVENT: &str = "signing_key_revoked";
+
+// Register an ed25519 public key the issuer signs certificates with
+pub fn register_signing_key(
+    env: &Env,
+    issuer: Address,
+    public_key: BytesN<32>,
+    valid_from: u64,
+    valid_until: Option<u64>,
+) -> Result<bool, Error> {
+    // Verify the caller is an authorized issuer
+    access_control::require_issuer(env, &issuer)?;
+    
+    // The all-zero key marks unsigned (legacy) certificates and can never be registered
+    if public_key == BytesN::from_array(env, &[0u8; 32]) {
+        return Err(Error::InvalidSigningKey);
+    }
+    
+    // Check the validity window
+    if let Some(until) = valid_until {
+        if until <= valid_from {
+            return Err(Error::InvalidValidityWindow);
+        }
+    }
+    
+    // Check if the key is already registered
+    let mut keys = storage::get_signing_keys(env, &issuer);
+    for key in keys.iter() {
+        if key.public_key == public_key {
+            return Ok(false);
+        }
+    }
+    
+    // Add the new key
+    keys.push_back(SigningKey {
+        public_key: public_key.clone(),
+        valid_from,
+        valid_until,
+        revoked: false,
+    });
+    storage::set_signing_keys(env, &issuer, &keys);
+    
+    // Emit signing key registered event
+    env.events().publish(
+        (Symbol::new(env, SIGNING_KEY_REGISTERED_EVENT),),
+        (issuer, public_key, valid_from, valid_until),
+    );
+    
+    Ok(true)
+}
+
+// Revoke a compromised signing key, invalidating every certificate it signed
+pub fn revoke_signing_key(
+    env: &Env,
+    issuer: Address,
+    public_key: BytesN<32>,
+) -> Result<bool, Error> {
+    // Verify the caller is an authorized issuer
+    access_control::require_issuer(env, &issuer)?;
+    
+    let mut keys = storage::get_signing_keys(env, &issuer);
+    for i in 0..keys.len() {
+        let mut key = keys.get(i).unwrap();
+        if key.public_key == public_key {
+            // Check if already revoked
+            if key.revoked {
+                return Ok(false);
+            }
+            
+            key.revoked = true;
+            keys.set(i, key);
+            storage::set_signing_keys(env, &issuer, &keys);
+            
+            // Emit signing key revoked event
+            env.events().publish(
+                (Symbol::new(env, SIGNING_KEY_REVOKED_EVENT),),
+                (issuer, public_key),
+            );
+            
+            return Ok(true);
+        }
+    }
+    
+    Err(Error::SigningKeyNotFound)
+}
+
+// Get all signing keys registered by an issuer
+pub fn get_signing_keys(env: &Env, issuer: Address) -> Vec<SigningKey> {
+    storage::get_signing_keys(env, &issuer)
+}
+
+// Find a registered signing key of an issuer
+pub fn find_signing_key(env: &Env, issuer: &Address, public_key: &BytesN<32>) -> Option<SigningKey> {
+    storage::get_signing_keys(env, issuer)
+        .iter()
+        .find(|key| key.public_key == *public_key)
+}
+
+// Check if a key was usable for signing at the given time
+pub fn is_valid_at(key: &SigningKey, timestamp: u64) -> bool {
+    if key.revoked || timestamp < key.valid_from {
+        return false;
+    }
+    
+    match key.valid_until {
+        Some(until) => timestamp < until,
+        None => true,
+    }
+}
@@ -0,0 +1,479 @@
+use crate::{
+    Certificate, CertificateId, CertificateVersion, Evidence, Revocation, SigningKey, Suspension,
+    TransferOffer, TransferPolicy,
+};
+use core::cmp;
+use soroban_sdk::{contracttype, Address, BytesN, Env, String, Symbol, Vec};
+
+// Instance storage keys (kept as symbols so existing deployments keep their config)
+const CERTIFICATE_COUNT_KEY: &str = "cert_count";
+
+// Slots per page of a certificate index
+pub const INDEX_PAGE_SIZE: u32 = 50;
+
+// Persistent storage keys, always keyed by the full certificate ID or address
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DataKey {
+    Certificate(CertificateId),
+    Index(IndexScope),
+    IndexPage(IndexScope, u32),
+    IndexSlot(IndexScope, CertificateId),
+    SigningKeys(Address),
+    Revocation(u32),
+    RevocationCount,
+    CertificateRevocation(CertificateId),
+    Suspension(CertificateId),
+    Predecessor(CertificateId),
+    Successor(CertificateId),
+    CertificateVersion(CertificateId, u32),
+    VersionCount(CertificateId),
+    CertificateTransferPolicy(CertificateId),
+    TypeTransferPolicy(String),
+    TransferOffer(CertificateId),
+    PendingOffers(Address),
+    Evidence(CertificateId),
+    MetadataRoot(CertificateId),
+    ExpirySweep,
+}
+
+// Check if a certificate exists
+pub fn has_certificate(env: &Env, certificate_id: &CertificateId) -> bool {
+    env.storage()
+        .persistent()
+        .has(&DataKey::Certificate(certificate_id.clone()))
+}
+
+// Get a certificate by its ID, if it exists
+pub fn get_certificate(env: &Env, certificate_id: &CertificateId) -> Option<Certificate> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Certificate(certificate_id.clone()))
+}
+
+// Store a certificate under its ID
+pub fn set_certificate(env: &Env, certificate: &Certificate) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Certificate(certificate.id.clone()), certificate);
+}
+
+// Certificates an index lists. Besides the certificates of each owner and
+// issuer, secondary indexes list them by certification type and achievement
+// type, and expiry indexes by the week they expire in.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum IndexScope {
+    Owner(Address),
+    Issuer(Address),
+    OwnerType(Address, String), // owner and certification type
+    IssuerType(Address, String),
+    OwnerAchievement(Address, String), // owner and achievement type
+    IssuerAchievement(Address, String),
+    Expiry(u64), // expiry week
+    OwnerExpiry(Address, u64),
+}
+
+// Size of a certificate index. Certificate IDs are appended to slots in
+// fixed-size pages, and slots never move, so a slot number is a stable listing
+// cursor.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CertificateIndex {
+    pub len: u32,   // slots used
+    pub count: u32, // certificates currently indexed
+}
+
+// Get the size of a certificate index
+pub fn get_index(env: &Env, scope: &IndexScope) -> CertificateIndex {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Index(scope.clone()))
+        .unwrap_or(CertificateIndex { len: 0, count: 0 })
+}
+
+// Get the certificate IDs in up to `limit` slots of an index from `cursor` on.
+// Slots of certificates that left the index are kept, so the IDs include them.
+pub fn get_index_slots(
+    env: &Env,
+    scope: &IndexScope,
+    cursor: u32,
+    limit: u32,
+) -> Vec<CertificateId> {
+    let mut cert_ids = Vec::new(env);
+    let end = cmp::min(cursor.saturating_add(limit), get_index(env, scope).len);
+    let mut slot = cursor;
+    while slot < end {
+        let page_number = slot / INDEX_PAGE_SIZE;
+        let page: Vec<CertificateId> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::IndexPage(scope.clone(), page_number))
+            .unwrap();
+        let page_end = cmp::min(end, (page_number + 1) * INDEX_PAGE_SIZE);
+        
+        for i in slot..page_end {
+            cert_ids.push_back(page.get(i % INDEX_PAGE_SIZE).unwrap());
+        }
+        slot = page_end;
+    }
+    
+    cert_ids
+}
+
+// Add a certificate to an index it can later leave. A certificate that comes
+// back takes its old slot again.
+pub fn add_to_index(env: &Env, scope: &IndexScope, cert_id: &CertificateId) {
+    let mut index = get_index(env, scope);
+    
+    if has_index_slot(env, scope, cert_id) {
+        index.count += 1;
+    } else {
+        append_slots(env, scope, &mut index, &Vec::from_array(env, [cert_id.clone()]));
+    }
+    env.storage()
+        .persistent()
+        .set(&DataKey::Index(scope.clone()), &index);
+}
+
+// Check whether a certificate's slot in an index was recorded
+pub fn has_index_slot(env: &Env, scope: &IndexScope, cert_id: &CertificateId) -> bool {
+    env.storage()
+        .persistent()
+        .has(&DataKey::IndexSlot(scope.clone(), cert_id.clone()))
+}
+
+// Record that a certificate has a slot in an index
+fn record_index_slot(env: &Env, scope: &IndexScope, cert_id: &CertificateId) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::IndexSlot(scope.clone(), cert_id.clone()), &());
+}
+
+// Remove a certificate from an index it is listed in. Only the count is
+// updated: the slot stays, and listings skip certificates that no longer match
+// the index. Slots are only recorded here, when a certificate leaves, so adding
+// a certificate writes no more than the index and its last page.
+pub fn remove_from_index(env: &Env, scope: &IndexScope, cert_id: &CertificateId) {
+    let mut index = get_index(env, scope);
+    
+    index.count = index.count.saturating_sub(1);
+    env.storage()
+        .persistent()
+        .set(&DataKey::Index(scope.clone()), &index);
+    record_index_slot(env, scope, cert_id);
+}
+
+// Append certificates to an index they never leave, such as an issuer's,
+// without recording their slots
+pub fn extend_index(env: &Env, scope: &IndexScope, cert_ids: &Vec<CertificateId>) {
+    let mut index = get_index(env, scope);
+    
+    append_slots(env, scope, &mut index, cert_ids);
+    env.storage()
+        .persistent()
+        .set(&DataKey::Index(scope.clone()), &index);
+}
+
+// Append certificate IDs to the slots of an index, filling its last page first.
+// Only the pages written are read.
+fn append_slots(
+    env: &Env,
+    scope: &IndexScope,
+    index: &mut CertificateIndex,
+    cert_ids: &Vec<CertificateId>,
+) {
+    if cert_ids.is_empty() {
+        return;
+    }
+    
+    let mut page_number = index.len / INDEX_PAGE_SIZE;
+    let mut page: Vec<CertificateId> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::IndexPage(scope.clone(), page_number))
+        .unwrap_or_else(|| Vec::new(env));
+    
+    for cert_id in cert_ids.iter() {
+        if page.len() == INDEX_PAGE_SIZE {
+            env.storage()
+                .persistent()
+                .set(&DataKey::IndexPage(scope.clone(), page_number), &page);
+            page_number += 1;
+            page = Vec::new(env);
+        }
+        page.push_back(cert_id);
+    }
+    env.storage()
+        .persistent()
+        .set(&DataKey::IndexPage(scope.clone(), page_number), &page);
+    
+    index.len += cert_ids.len();
+    index.count += cert_ids.len();
+}
+
+// Get the signing keys registered by an issuer
+pub fn get_signing_keys(env: &Env, issuer: &Address) -> Vec<SigningKey> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::SigningKeys(issuer.clone()))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+// Store the signing keys of an issuer
+pub fn set_signing_keys(env: &Env, issuer: &Address, keys: &Vec<SigningKey>) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::SigningKeys(issuer.clone()), keys);
+}
+
+// Get the number of revocations recorded in the registry
+pub fn get_revocation_count(env: &Env) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::RevocationCount)
+        .unwrap_or(0)
+}
+
+// Get a revocation by its sequence number
+pub fn get_revocation(env: &Env, sequence: u32) -> Option<Revocation> {
+    env.storage().persistent().get(&DataKey::Revocation(sequence))
+}
+
+// Append a revocation to the registry and return its sequence number
+pub fn add_revocation(env: &Env, revocation: &Revocation) -> u32 {
+    let sequence = get_revocation_count(env);
+    
+    env.storage()
+        .persistent()
+        .set(&DataKey::Revocation(sequence), revocation);
+    env.storage()
+        .persistent()
+        .set(&DataKey::CertificateRevocation(revocation.certificate_id.clone()), &sequence);
+    env.storage()
+        .persistent()
+        .set(&DataKey::RevocationCount, &(sequence + 1));
+    
+    sequence
+}
+
+// Get the registry sequence number of a certificate's revocation
+pub fn get_certificate_revocation(env: &Env, certificate_id: &CertificateId) -> Option<u32> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::CertificateRevocation(certificate_id.clone()))
+}
+
+// Get the suspension recorded for a certificate
+pub fn get_suspension(env: &Env, certificate_id: &CertificateId) -> Option<Suspension> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Suspension(certificate_id.clone()))
+}
+
+// Record the suspension of a certificate
+pub fn set_suspension(env: &Env, certificate_id: &CertificateId, suspension: &Suspension) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Suspension(certificate_id.clone()), suspension);
+}
+
+// Get the certificate a renewal replaced
+pub fn get_predecessor(env: &Env, certificate_id: &CertificateId) -> Option<CertificateId> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Predecessor(certificate_id.clone()))
+}
+
+// Get the certificate that renewed this one
+pub fn get_successor(env: &Env, certificate_id: &CertificateId) -> Option<CertificateId> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Successor(certificate_id.clone()))
+}
+
+// Link a renewal to the certificate it replaced, in both directions
+pub fn link_renewal(env: &Env, predecessor: &CertificateId, successor: &CertificateId) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Predecessor(successor.clone()), predecessor);
+    env.storage()
+        .persistent()
+        .set(&DataKey::Successor(predecessor.clone()), successor);
+}
+
+// Get the number of versions recorded for a certificate (0 until the first
+// amendment or transfer)
+pub fn get_version_count(env: &Env, certificate_id: &CertificateId) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::VersionCount(certificate_id.clone()))
+        .unwrap_or(0)
+}
+
+// Get a recorded version of a certificate
+pub fn get_certificate_version(
+    env: &Env,
+    certificate_id: &CertificateId,
+    version: u32,
+) -> Option<CertificateVersion> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::CertificateVersion(certificate_id.clone(), version))
+}
+
+// Record the next version of a certificate
+pub fn add_certificate_version(env: &Env, certificate_id: &CertificateId, version: &CertificateVersion) {
+    env.storage().persistent().set(
+        &DataKey::CertificateVersion(certificate_id.clone(), version.version),
+        version,
+    );
+    env.storage()
+        .persistent()
+        .set(&DataKey::VersionCount(certificate_id.clone()), &version.version);
+}
+
+// Get the transfer policy set on a certificate
+pub fn get_certificate_transfer_policy(env: &Env, certificate_id: &CertificateId) -> Option<TransferPolicy> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::CertificateTransferPolicy(certificate_id.clone()))
+}
+
+// Set the transfer policy of a certificate
+pub fn set_certificate_transfer_policy(env: &Env, certificate_id: &CertificateId, policy: TransferPolicy) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::CertificateTransferPolicy(certificate_id.clone()), &policy);
+}
+
+// Get the transfer policy set for a certification type
+pub fn get_type_transfer_policy(env: &Env, cert_type_id: &String) -> Option<TransferPolicy> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::TypeTransferPolicy(cert_type_id.clone()))
+}
+
+// Set the transfer policy of a certification type
+pub fn set_type_transfer_policy(env: &Env, cert_type_id: &String, policy: TransferPolicy) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::TypeTransferPolicy(cert_type_id.clone()), &policy);
+}
+
+// Get the pending transfer offer for a certificate
+pub fn get_transfer_offer(env: &Env, certificate_id: &CertificateId) -> Option<TransferOffer> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::TransferOffer(certificate_id.clone()))
+}
+
+// Store the transfer offer for a certificate and index it under both parties
+pub fn set_transfer_offer(env: &Env, offer: &TransferOffer) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::TransferOffer(offer.certificate_id.clone()), offer);
+    add_to_pending_offers(env, &offer.from, &offer.certificate_id);
+    add_to_pending_offers(env, &offer.to, &offer.certificate_id);
+}
+
+// Remove the transfer offer for a certificate from storage and both indexes
+pub fn remove_transfer_offer(env: &Env, offer: &TransferOffer) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::TransferOffer(offer.certificate_id.clone()));
+    remove_from_pending_offers(env, &offer.from, &offer.certificate_id);
+    remove_from_pending_offers(env, &offer.to, &offer.certificate_id);
+}
+
+// Get the certificates with a pending offer from or to an address
+pub fn get_pending_offers(env: &Env, address: &Address) -> Vec<CertificateId> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PendingOffers(address.clone()))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+// Add a certificate to an address's pending offers
+fn add_to_pending_offers(env: &Env, address: &Address, cert_id: &CertificateId) {
+    let mut pending = get_pending_offers(env, address);
+    
+    pending.push_back(cert_id.clone());
+    env.storage()
+        .persistent()
+        .set(&DataKey::PendingOffers(address.clone()), &pending);
+}
+
+// Remove a certificate from an address's pending offers
+fn remove_from_pending_offers(env: &Env, address: &Address, cert_id: &CertificateId) {
+    let mut pending = get_pending_offers(env, address);
+    
+    if let Some(index) = pending.first_index_of(cert_id) {
+        pending.remove(index);
+        env.storage()
+            .persistent()
+            .set(&DataKey::PendingOffers(address.clone()), &pending);
+    }
+}
+
+// Get the evidence submitted for a certificate
+pub fn get_evidence(env: &Env, certificate_id: &CertificateId) -> Vec<Evidence> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Evidence(certificate_id.clone()))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+// Store the evidence submitted for a certificate
+pub fn set_evidence(env: &Env, certificate_id: &CertificateId, evidence: &Vec<Evidence>) {
+    if !evidence.is_empty() {
+        env.storage()
+            .persistent()
+            .set(&DataKey::Evidence(certificate_id.clone()), evidence);
+    }
+}
+
+// Get the Merkle root the issuer committed over a certificate's metadata fields
+pub fn get_metadata_root(env: &Env, certificate_id: &CertificateId) -> Option<BytesN<32>> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::MetadataRoot(certificate_id.clone()))
+}
+
+// Store the Merkle root the issuer committed over a certificate's metadata fields
+pub fn set_metadata_root(env: &Env, certificate_id: &CertificateId, root: &BytesN<32>) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::MetadataRoot(certificate_id.clone()), root);
+}
+
+// Position of the expiry sweep in the expiry index
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExpirySweep {
+    pub bucket: u64,
+    pub index: u32,
+}
+
+// Get the position of the expiry sweep, if any certificate was ever indexed
+pub fn get_expiry_sweep(env: &Env) -> Option<ExpirySweep> {
+    env.storage().persistent().get(&DataKey::ExpirySweep)
+}
+
+// Store the position of the expiry sweep
+pub fn set_expiry_sweep(env: &Env, sweep: &ExpirySweep) {
+    env.storage().persistent().set(&DataKey::ExpirySweep, sweep);
+}
+
+// Increment the total certificate count
+pub fn increment_certificate_count(env: &Env) {
+    let count_key = Symbol::new(env, CERTIFICATE_COUNT_KEY);
+    let current_count: u32 = env.storage().instance().get(&count_key).unwrap_or(0);
+    env.storage().instance().set(&count_key, &(current_count + 1));
+}
+
+// Get the total certificate count
+pub fn get_certificate_count(env: &Env) -> u32 {
+    let count_key = Symbol::new(env, CERTIFICATE_COUNT_KEY);
+    env.storage().instance().get(&count_key).unwrap_or(0)
+}
@@ -1,17 +1,27 @@
 #![cfg(test)]
+extern crate std;
 
 use super::*;
 use soroban_sdk::{
-    testutils::{Address as _, Ledger as _},
-    Address, Bytes, BytesN, Env, Map, String, vec,
+    testutils::{Address as _, Events as _, Ledger as _, MockAuth, MockAuthInvoke},
+    token::{StellarAssetClient, TokenClient},
+    Address, Bytes, BytesN, Env, IntoVal, Map, String, Symbol, Val, vec,
 };
+use certification_type_management::{
+    CertificationTypeManagementContract, CertificationTypeManagementContractClient,
+};
+use authority_contract::{CertifyingAuthority, CertifyingAuthorityClient};
+use entity_contract::{CertifiableEntity, CertifiableEntityClient};
+use issuance_guard::IssuanceGuard;
+use ed25519_dalek::{Signer, SigningKey as Ed25519Key};
 
 // Helper function to create a test environment
 fn create_test_env() -> Env {
     let env = Env::default();
     env.ledger().with_mut(|ledger| {
         ledger.timestamp = 12345;
-        ledger.protocol_version = 20;
+        // Constructors, which the certification type registry has, need protocol 22
+        ledger.protocol_version = 22;
         ledger.sequence_number = 10;
     });
     env
@@ -34,7 +44,7 @@ fn create_test_metadata(env: &Env) -> CertificateMetadata {
 }
 
 // Helper to setup a contract with an admin
-fn setup_contract(env: &Env) -> (Address, CertificationContractClient) {
+fn setup_contract(env: &Env) -> (Address, CertificationContractClient<'_>) {
     let admin = Address::generate(env);
     
     // Initialize the contract
@@ -50,9 +60,243 @@ fn setup_contract(env: &Env) -> (Address, CertificationContractClient) {
     // Initialize the contract
     client.initialize(&admin);
     
+    // Register a certification type and point the contract at the registry
+    let registry_id = env.register(CertificationTypeManagementContract, (admin.clone(),));
+    let registry = CertificationTypeManagementContractClient::new(env, &registry_id);
+    registry.register_certification_type(
+        &String::from_str(env, "ISO_9001"),
+        &String::from_str(env, "2015"),
+        &String::from_str(env, "Quality Management System standard"),
+        &vec![env, String::from_str(env, "Manufacturing")],
+        &String::from_str(env, "3 years"),
+    );
+    client.set_certification_type_registry(&registry_id);
+    
+    // Point the contract at the authority registry
+    let authority_registry_id = env.register(CertifyingAuthority, ());
+    CertifyingAuthorityClient::new(env, &authority_registry_id).initialize(&admin);
+    client.set_authority_registry(&authority_registry_id);
+    
+    // Point the contract at the guard it checks signatures with
+    client.set_issuance_guard(&env.register(IssuanceGuard, ()));
+    
     (admin, client)
 }
 
+// Helper to get the ID of the certification type registered by setup_contract
+fn cert_type_id(env: &Env) -> String {
+    String::from_str(env, "CERT_TYPE_A")
+}
+
+// Helper to register an issuer as an authority accredited for the test certification type
+fn accredit_issuer(env: &Env, contract: &CertificationContractClient, issuer: &Address) {
+    let registry = CertifyingAuthorityClient::new(env, &contract.get_authority_registry());
+    
+    env.mock_all_auths();
+    if registry.try_get_authority(issuer).is_err() {
+        registry.register_authority(
+            &String::from_str(env, "Quality Certification Services Inc."),
+            issuer,
+            &String::from_str(env, "ISO 17021:2015"),
+            &vec![env, cert_type_id(env)],
+        );
+    }
+}
+
+// Helper to let certificates of the test certification type change hands, which
+// they may not by default
+fn allow_transfers(env: &Env, contract: &CertificationContractClient) {
+    env.mock_all_auths();
+    contract.set_type_transfer_policy(&cert_type_id(env), &TransferPolicy::OwnerTransferable);
+}
+
+// Helper to register an ed25519 signing key for an accredited issuer
+fn create_signing_key(
+    env: &Env,
+    contract: &CertificationContractClient,
+    issuer: &Address,
+    seed: u8,
+) -> Ed25519Key {
+    let signing_key = Ed25519Key::from_bytes(&[seed; 32]);
+    
+    accredit_issuer(env, contract, issuer);
+    contract.register_signing_key(issuer, &public_key(env, &signing_key), &0, &None);
+    
+    signing_key
+}
+
+// Helper to get the public key an issuer registers for a signing key
+fn public_key(env: &Env, signing_key: &Ed25519Key) -> BytesN<32> {
+    BytesN::from_array(env, &signing_key.verifying_key().to_bytes())
+}
+
+// Helper to list the fields of certificate metadata: the title, the description,
+// the achievement type, then each additional data entry in key order
+fn metadata_fields(env: &Env, metadata: &CertificateMetadata) -> Vec<DisclosedField> {
+    let mut fields = vec![
+        env,
+        DisclosedField::Title(metadata.title.clone()),
+        DisclosedField::Description(metadata.description.clone()),
+        DisclosedField::AchievementType(metadata.achievement_type.clone()),
+    ];
+    for (key, value) in metadata.additional_data.iter() {
+        fields.push_back(DisclosedField::AdditionalData(key, value));
+    }
+    
+    fields
+}
+
+// Helper to salt fields and hash them as leaves, the way an issuer commits to
+// them off-chain. Returns the salts, which the holder keeps, and the leaves.
+fn commit_fields(env: &Env, fields: &Vec<DisclosedField>) -> (Vec<BytesN<32>>, Vec<BytesN<32>>) {
+    let mut salts = Vec::new(env);
+    let mut leaves = Vec::new(env);
+    for (i, field) in fields.iter().enumerate() {
+        let salt = BytesN::from_array(env, &[0xA0 + i as u8; 32]);
+        leaves.push_back(disclosure::leaf_hash(env, &salt, &field));
+        salts.push_back(salt);
+    }
+    
+    (salts, leaves)
+}
+
+// Helper to hash each pair of nodes on a level into the level above. An odd
+// node at the end moves up unchanged.
+fn parent_level(env: &Env, level: &Vec<BytesN<32>>) -> Vec<BytesN<32>> {
+    let mut parents = Vec::new(env);
+    for i in (0..level.len()).step_by(2) {
+        let node = level.get(i).unwrap();
+        match level.get(i + 1) {
+            Some(sibling) => parents.push_back(disclosure::node_hash(env, &node, &sibling)),
+            None => parents.push_back(node),
+        }
+    }
+    
+    parents
+}
+
+// Helper to build the Merkle root over committed leaves
+fn merkle_root(env: &Env, leaves: &Vec<BytesN<32>>) -> BytesN<32> {
+    let mut level = leaves.clone();
+    while level.len() > 1 {
+        level = parent_level(env, &level);
+    }
+    
+    level.get(0).unwrap()
+}
+
+// Helper to build the proof of the leaf at `index`: its sibling on each level, bottom up
+fn merkle_proof(env: &Env, leaves: &Vec<BytesN<32>>, mut index: u32) -> Vec<BytesN<32>> {
+    let mut proof = Vec::new(env);
+    let mut level = leaves.clone();
+    while level.len() > 1 {
+        if let Some(sibling) = level.get(index ^ 1) {
+            proof.push_back(sibling);
+        }
+        level = parent_level(env, &level);
+        index /= 2;
+    }
+    
+    proof
+}
+
+// Helper to get the root an issuer commits for certificate metadata that has
+// nothing kept off-chain
+fn metadata_root(env: &Env, metadata: &CertificateMetadata) -> BytesN<32> {
+    let (_, leaves) = commit_fields(env, &metadata_fields(env, metadata));
+    merkle_root(env, &leaves)
+}
+
+// Helper to sign certificate content the way an issuer does off-chain
+fn sign_certificate(
+    env: &Env,
+    signing_key: &Ed25519Key,
+    issuer: &Address,
+    owner: &Address,
+    metadata: &CertificateMetadata,
+    expiration_date: Option<u64>,
+) -> BytesN<64> {
+    let root = metadata_root(env, metadata);
+    sign_committed_certificate(env, signing_key, issuer, owner, metadata, &root, expiration_date)
+}
+
+// Helper to sign certificate content committed to under a given metadata root
+fn sign_committed_certificate(
+    env: &Env,
+    signing_key: &Ed25519Key,
+    issuer: &Address,
+    owner: &Address,
+    metadata: &CertificateMetadata,
+    metadata_root: &BytesN<32>,
+    expiration_date: Option<u64>,
+) -> BytesN<64> {
+    let message = verification::create_verification_message(
+        env,
+        owner,
+        issuer,
+        &cert_type_id(env),
+        metadata,
+        Some(metadata_root),
+        expiration_date,
+        None,
+    );
+    let signature = signing_key.sign(message.to_buffer::<32>().as_slice());
+    BytesN::from_array(env, &signature.to_bytes())
+}
+
+// Helper to sign the renewal of a certificate
+fn sign_renewal(
+    env: &Env,
+    signing_key: &Ed25519Key,
+    certificate: &Certificate,
+    new_expiration: Option<u64>,
+) -> BytesN<64> {
+    let message = verification::create_verification_message(
+        env,
+        &certificate.owner,
+        &certificate.issuer,
+        &certificate.cert_type_id,
+        &certificate.metadata,
+        Some(&metadata_root(env, &certificate.metadata)),
+        new_expiration,
+        Some(&certificate.id),
+    );
+    let signature = signing_key.sign(message.to_buffer::<32>().as_slice());
+    BytesN::from_array(env, &signature.to_bytes())
+}
+
+// Helper to sign an amendment of a certificate
+fn sign_amendment(
+    env: &Env,
+    signing_key: &Ed25519Key,
+    certificate: &Certificate,
+    metadata: &CertificateMetadata,
+    version: u32,
+) -> BytesN<64> {
+    let content_message = verification::create_verification_message(
+        env,
+        &certificate.owner,
+        &certificate.issuer,
+        &certificate.cert_type_id,
+        metadata,
+        Some(&metadata_root(env, metadata)),
+        certificate.expiration_date,
+        None,
+    );
+    let message =
+        verification::create_amendment_message(env, &content_message, &certificate.id, version);
+    let signature = signing_key.sign(message.to_buffer::<32>().as_slice());
+    BytesN::from_array(env, &signature.to_bytes())
+}
+
+// Helper to get the certificate ID of a successful batch item
+fn issued_id(result: BatchItemResult) -> CertificateId {
+    match result {
+        BatchItemResult::Success(cert_id) => cert_id,
+        BatchItemResult::Failure(code) => panic!("batch item failed with error {}", code),
+    }
+}
+
 #[test]
 fn test_initialize_contract() {
     let env = create_test_env();
@@ -60,6 +304,11 @@ fn test_initialize_contract() {
     
     // Verify the admin is set correctly
     assert!(contract.is_issuer(&admin));
+    
+    // Initialization needs the authorization of the admin it names
+    let fresh = CertificationContractClient::new(&env, &env.register(CertificationContract, ()));
+    env.set_auths(&[]);
+    assert!(fresh.try_initialize(&Address::generate(&env)).is_err());
 }
 
 #[test]
@@ -71,27 +320,28 @@ fn test_issue_certificate() {
     // Create test metadata
     let metadata = create_test_metadata(&env);
     
-    // Create a dummy signature
-    let signature = Bytes::from_slice(&env, &[0u8; 64]);
+    // Register the admin's signing key
+    let signing_key = create_signing_key(&env, &contract, &admin, 1);
     
     // Set the admin as the invoker for the issue_certificate call
     env.mock_all_auths();
     
     // Issue a certificate
-    let cert_id = contract.issue_certificate(&owner, &metadata, &None, &signature);
+    let signature = sign_certificate(&env, &signing_key, &admin, &owner, &metadata, None);
+    let cert_id = contract.issue_certificate(&admin, &owner, &cert_type_id(&env), &metadata, &metadata_root(&env, &metadata), &None, &vec![&env], &public_key(&env, &signing_key), &signature);
     
     // Verify the certificate was created
     let certificate = contract.get_certificate(&cert_id);
     assert_eq!(certificate.owner, owner);
     assert_eq!(certificate.issuer, admin);
     assert_eq!(certificate.metadata.title, metadata.title);
-    assert_eq!(certificate.revoked, false);
+    assert_eq!(certificate.status, CertificateStatus::Active);
 }
 
 #[test]
 fn test_batch_issue_certificates() {
     let env = create_test_env();
-    let (_admin, contract) = setup_contract(&env);
+    let (admin, contract) = setup_contract(&env);
     
     // Create multiple owners
     let owner1 = Address::generate(&env);
@@ -102,50 +352,225 @@ fn test_batch_issue_certificates() {
     let mut metadata2 = metadata1.clone();
     metadata2.title = String::from_str(&env, "Advanced Data Science Certificate");
     
-    // Create dummy signatures
-    let signature1 = Bytes::from_slice(&env, &[0u8; 64]);
-    let signature2 = Bytes::from_slice(&env, &[1u8; 64]);
+    // Sign both certificates with the admin's key
+    let signing_key = create_signing_key(&env, &contract, &admin, 1);
+    let signature1 = sign_certificate(&env, &signing_key, &admin, &owner1, &metadata1, None);
+    let signature2 = sign_certificate(&env, &signing_key, &admin, &owner2, &metadata2, None);
     
     // Create the input vectors
     let owners = vec![&env, owner1.clone(), owner2.clone()];
+    let cert_type_ids = vec![&env, cert_type_id(&env), cert_type_id(&env)];
     let metadatas = vec![&env, metadata1.clone(), metadata2.clone()];
+    let metadata_roots = vec![&env, metadata_root(&env, &metadata1), metadata_root(&env, &metadata2)];
     let expiration_dates = vec![&env, None, None];
     let signatures = vec![&env, signature1.clone(), signature2.clone()];
     
-    // Set the admin as the invoker for the batch_issue_certificates call
-    env.mock_all_auths();
+    // Set the admin as the invoker for the batch_issue_certificates call. The
+    // owners authorize their certificates through the guard, below the batch call.
+    env.mock_all_auths_allowing_non_root_auth();
     
     // Batch issue certificates
-    let cert_ids = contract.batch_issue_certificates(&owners, &metadatas, &expiration_dates, &signatures);
+    let results = contract.batch_issue_certificates(
+        &admin,
+        &owners,
+        &cert_type_ids,
+        &metadatas,
+        &metadata_roots,
+        &expiration_dates,
+        &vec![&env, vec![&env], vec![&env]],
+        &vec![&env, public_key(&env, &signing_key), public_key(&env, &signing_key)],
+        &signatures,
+        &BatchMode::AllOrNothing,
+    );
     
     // Verify the certificates were created
-    assert_eq!(cert_ids.len(), 2);
+    assert_eq!(results.len(), 2);
     
-    let certificate1 = contract.get_certificate(&cert_ids.get(0).unwrap());
+    let certificate1 = contract.get_certificate(&issued_id(results.get(0).unwrap()));
     assert_eq!(certificate1.owner, owner1);
     assert_eq!(certificate1.metadata.title, metadata1.title);
     
-    let certificate2 = contract.get_certificate(&cert_ids.get(1).unwrap());
+    let certificate2 = contract.get_certificate(&issued_id(results.get(1).unwrap()));
     assert_eq!(certificate2.owner, owner2);
     assert_eq!(certificate2.metadata.title, metadata2.title);
 }
 
+#[test]
+fn test_certificates_do_not_collide() {
+    let env = create_test_env();
+    let (admin, contract) = setup_contract(&env);
+    let metadata = create_test_metadata(&env);
+    let signing_key = create_signing_key(&env, &contract, &admin, 1);
+    
+    env.mock_all_auths();
+    
+    // Issue enough certificates that the old one-byte keys were bound to collide
+    let mut owners = Vec::new(&env);
+    let mut cert_ids = Vec::new(&env);
+    let mut seen_slots = [false; 256];
+    let mut collided = false;
+    for _ in 0..40 {
+        let owner = Address::generate(&env);
+        let signature = sign_certificate(&env, &signing_key, &admin, &owner, &metadata, None);
+        let cert_id = contract.issue_certificate(&admin, &owner, &cert_type_id(&env), &metadata, &metadata_root(&env, &metadata), &None, &vec![&env], &public_key(&env, &signing_key), &signature);
+        
+        let slot = env.crypto().sha256(&Bytes::from_slice(&env, &cert_id.0.to_array())).to_array()[0];
+        collided |= seen_slots[slot as usize];
+        seen_slots[slot as usize] = true;
+        
+        owners.push_back(owner);
+        cert_ids.push_back(cert_id);
+    }
+    assert!(collided);
+    
+    // Every certificate must still be stored and indexed under its own owner
+    assert_eq!(contract.get_certificate_count(), 40);
+    for i in 0..cert_ids.len() {
+        let cert_id = cert_ids.get(i).unwrap();
+        let owner = owners.get(i).unwrap();
+        assert_eq!(contract.get_certificate(&cert_id).owner, owner);
+        assert_eq!(contract.count_certificates_by_owner(&owner), 1);
+    }
+}
+
+#[test]
+fn test_migrate_legacy_storage() {
+    let env = create_test_env();
+    let (admin, contract) = setup_contract(&env);
+    let owner = Address::generate(&env);
+    
+    // Write a certificate the way the one-byte key layout stored it
+    let cert_id = CertificateId(BytesN::from_array(&env, &[7u8; 32]));
+    let certificate = migration::LegacyCertificate {
+        id: cert_id.clone(),
+        owner: owner.clone(),
+        issuer: admin.clone(),
+        metadata: create_test_metadata(&env),
+        issuance_date: env.ledger().timestamp(),
+        expiration_date: None,
+        revoked: false,
+        signature: Bytes::from_slice(&env, &[0u8; 64]),
+    };
+    let revoked_id = CertificateId(BytesN::from_array(&env, &[9u8; 32]));
+    let revoked_certificate = migration::LegacyCertificate {
+        id: revoked_id.clone(),
+        revoked: true,
+        ..certificate.clone()
+    };
+    let revoked_slot = env.crypto().sha256(&Bytes::from_slice(&env, &revoked_id.0.to_array())).to_array()[0];
+    let slot = env.crypto().sha256(&Bytes::from_slice(&env, &cert_id.0.to_array())).to_array()[0];
+    let legacy_cert_key = Symbol::new(&env, &alloc::format!("cert{:x}", slot));
+    let legacy_owner_key = Symbol::new(&env, "owner_certs_0");
+    env.as_contract(&contract.address, || {
+        env.storage().persistent().set(&legacy_cert_key, &certificate);
+        env.storage().persistent().set(&Symbol::new(&env, &alloc::format!("cert{:x}", revoked_slot)), &revoked_certificate);
+        env.storage().persistent().set(&legacy_owner_key, &vec![&env, cert_id.clone()]);
+    });
+    
+    // Migrate every legacy slot in two batches
+    env.mock_all_auths();
+    let migrated = contract.migrate_legacy_storage(&0, &128) + contract.migrate_legacy_storage(&128, &128);
+    assert_eq!(migrated, 2);
+    
+    // The certificate is readable and indexed under the typed keys
+    let migrated_certificate = contract.get_certificate(&cert_id);
+    assert_eq!(migrated_certificate.owner, owner);
+    assert_eq!(migrated_certificate.metadata, certificate.metadata);
+    assert_eq!(migrated_certificate.status, CertificateStatus::Active);
+    assert_eq!(contract.count_certificates_by_owner(&owner), 2);
+    assert_eq!(contract.count_certificates_by_issuer(&admin), 2);
+    
+    // Legacy signatures were never checked, so the certificate reports a bad
+    // signature instead of verifying; it predates certification types
+    assert_eq!(migrated_certificate.signing_key, BytesN::from_array(&env, &[0u8; 32]));
+    assert_eq!(migrated_certificate.cert_type_id, String::from_str(&env, ""));
+    assert_eq!(contract.verify_certificate(&cert_id).status, VerificationStatus::BadSignature);
+    assert!(!contract.verify_certificate_signature(&cert_id));
+    
+    // Revoked legacy certificates are withdrawn, but the legacy layout kept no
+    // reason or date, so they have no revocation registry entry
+    assert_eq!(contract.get_certificate(&revoked_id).status, CertificateStatus::Withdrawn);
+    assert_eq!(contract.verify_certificate(&revoked_id).status, VerificationStatus::Revoked);
+    assert_eq!(contract.try_get_revocation(&revoked_id), Err(Ok(Error::RevocationNotFound)));
+    
+    // The legacy entries are gone
+    env.as_contract(&contract.address, || {
+        assert!(!env.storage().persistent().has(&legacy_cert_key));
+        assert!(!env.storage().persistent().has(&legacy_owner_key));
+    });
+}
+
+#[test]
+fn test_upgrade_and_migrate() {
+    let env = create_test_env();
+    let (admin, contract) = setup_contract(&env);
+    let schema_version_key = Symbol::new(&env, "schema_version");
+    let set_stored_version = |version: u32| {
+        env.as_contract(&contract.address, || env.storage().instance().set(&schema_version_key, &version));
+    };
+    let migrated_events = || {
+        let topics: Vec<Val> = (Symbol::new(&env, "schema_migrated"),).into_val(&env);
+        env.events().all().iter().filter(|event| event.1 == topics).count()
+    };
+    
+    // A new deployment starts at the current schema version, so there is nothing to migrate
+    assert_eq!(contract.get_schema_version(), upgrade::SCHEMA_VERSION);
+    env.mock_all_auths();
+    assert_eq!(contract.migrate(), upgrade::SCHEMA_VERSION);
+    assert_eq!(migrated_events(), 0);
+    
+    // Deployments from before schema versioning are on the legacy layout, and
+    // are only brought to version 1 once every legacy slot has been migrated
+    env.as_contract(&contract.address, || env.storage().instance().remove(&schema_version_key));
+    assert_eq!(contract.get_schema_version(), 0);
+    assert_eq!(contract.migrate(), 0);
+    contract.migrate_legacy_storage(&0, &128);
+    contract.migrate_legacy_storage(&200, &56);
+    assert_eq!(contract.migrate(), 0);
+    assert_eq!(migrated_events(), 0);
+    contract.migrate_legacy_storage(&128, &128);
+    assert_eq!(contract.migrate(), upgrade::SCHEMA_VERSION);
+    assert_eq!(migrated_events(), 1);
+    assert_eq!(contract.get_schema_version(), upgrade::SCHEMA_VERSION);
+    
+    // Code older than the stored data refuses to migrate it
+    set_stored_version(upgrade::SCHEMA_VERSION + 1);
+    assert_eq!(contract.try_migrate(), Err(Ok(Error::SchemaVersionTooNew)));
+    set_stored_version(upgrade::SCHEMA_VERSION);
+    
+    // A test contract runs the empty Wasm, so upgrading to it succeeds, but
+    // only with the admin's authorization
+    let wasm_hash: BytesN<32> = env.crypto().sha256(&Bytes::new(&env)).into();
+    env.set_auths(&[]);
+    assert!(contract.try_upgrade(&wasm_hash).is_err());
+    assert!(contract.try_migrate().is_err());
+    env.mock_all_auths();
+    contract.upgrade(&wasm_hash);
+    assert_eq!(env.auths()[0].0, admin);
+    
+    // Only code that was uploaded can be installed
+    let unknown_hash = BytesN::from_array(&env, &[1u8; 32]);
+    assert!(contract.try_upgrade(&unknown_hash).is_err());
+}
+
 #[test]
 fn test_transfer_certificate() {
     let env = create_test_env();
-    let (_admin, contract) = setup_contract(&env);
+    let (admin, contract) = setup_contract(&env);
+    allow_transfers(&env, &contract);
     let original_owner = Address::generate(&env);
     let new_owner = Address::generate(&env);
     
     // Create test metadata
     let metadata = create_test_metadata(&env);
-    let signature = Bytes::from_slice(&env, &[0u8; 64]);
+    let signing_key = create_signing_key(&env, &contract, &admin, 1);
     
     // Set the admin as the invoker for the issue_certificate call
     env.mock_all_auths();
     
     // Issue a certificate
-    let cert_id = contract.issue_certificate(&original_owner, &metadata, &None, &signature);
+    let signature = sign_certificate(&env, &signing_key, &admin, &original_owner, &metadata, None);
+    let cert_id = contract.issue_certificate(&admin, &original_owner, &cert_type_id(&env), &metadata, &metadata_root(&env, &metadata), &None, &vec![&env], &public_key(&env, &signing_key), &signature);
     
     // Mock the original owner's authorization for transfer
     env.mock_all_auths();
@@ -156,12 +581,16 @@ fn test_transfer_certificate() {
     // Verify the transfer
     let certificate = contract.get_certificate(&cert_id);
     assert_eq!(certificate.owner, new_owner);
+    
+    // The signature still covers the owner it was issued to
+    assert!(contract.verify_certificate_signature(&cert_id));
+    assert_eq!(contract.get_certificate_version(&cert_id, &1).owner, original_owner);
 }
 
 #[test]
 fn test_list_certificates_by_owner() {
     let env = create_test_env();
-    let (_admin, contract) = setup_contract(&env);
+    let (admin, contract) = setup_contract(&env);
     let owner = Address::generate(&env);
     
     // Create test metadata
@@ -169,20 +598,24 @@ fn test_list_certificates_by_owner() {
     let mut metadata2 = metadata1.clone();
     metadata2.title = String::from_str(&env, "Advanced Data Science Certificate");
     
-    let signature = Bytes::from_slice(&env, &[0u8; 64]);
+    let signing_key = create_signing_key(&env, &contract, &admin, 1);
     
     // Set the admin as the invoker for the issue_certificate calls
     env.mock_all_auths();
     
     // Issue certificates
-    let cert_id1 = contract.issue_certificate(&owner, &metadata1, &None, &signature);
-    let cert_id2 = contract.issue_certificate(&owner, &metadata2, &None, &signature);
+    let signature = sign_certificate(&env, &signing_key, &admin, &owner, &metadata1, None);
+    let cert_id1 = contract.issue_certificate(&admin, &owner, &cert_type_id(&env), &metadata1, &metadata_root(&env, &metadata1), &None, &vec![&env], &public_key(&env, &signing_key), &signature);
+    let signature = sign_certificate(&env, &signing_key, &admin, &owner, &metadata2, None);
+    let cert_id2 = contract.issue_certificate(&admin, &owner, &cert_type_id(&env), &metadata2, &metadata_root(&env, &metadata2), &None, &vec![&env], &public_key(&env, &signing_key), &signature);
     
     // List certificates by owner
-    let certificates = contract.list_certificates_by_owner(&owner, &0, &10);
+    let page = contract.list_certificates_by_owner(&owner, &0, &10);
+    let certificates = page.certificates;
     
     // Verify the list
     assert_eq!(certificates.len(), 2);
+    assert_eq!(page.next_cursor, None);
     
     // Check if both certificates are in the list
     let has_cert1 = certificates.iter().any(|cert| cert.id == cert_id1);
@@ -201,20 +634,24 @@ fn test_list_certificates_by_issuer() {
     
     // Create test metadata
     let metadata = create_test_metadata(&env);
-    let signature = Bytes::from_slice(&env, &[0u8; 64]);
+    let signing_key = create_signing_key(&env, &contract, &admin, 1);
     
     // Set the admin as the invoker for the issue_certificate calls
     env.mock_all_auths();
     
     // Issue certificates
-    let cert_id1 = contract.issue_certificate(&owner1, &metadata, &None, &signature);
-    let cert_id2 = contract.issue_certificate(&owner2, &metadata, &None, &signature);
+    let signature = sign_certificate(&env, &signing_key, &admin, &owner1, &metadata, None);
+    let cert_id1 = contract.issue_certificate(&admin, &owner1, &cert_type_id(&env), &metadata, &metadata_root(&env, &metadata), &None, &vec![&env], &public_key(&env, &signing_key), &signature);
+    let signature = sign_certificate(&env, &signing_key, &admin, &owner2, &metadata, None);
+    let cert_id2 = contract.issue_certificate(&admin, &owner2, &cert_type_id(&env), &metadata, &metadata_root(&env, &metadata), &None, &vec![&env], &public_key(&env, &signing_key), &signature);
     
     // List certificates by issuer
-    let certificates = contract.list_certificates_by_issuer(&admin, &0, &10);
+    let page = contract.list_certificates_by_issuer(&admin, &0, &10);
+    let certificates = page.certificates;
     
     // Verify the list
     assert_eq!(certificates.len(), 2);
+    assert_eq!(page.next_cursor, None);
     
     // Check if both certificates are in the list
     let has_cert1 = certificates.iter().any(|cert| cert.id == cert_id1);
@@ -222,6 +659,209 @@ fn test_list_certificates_by_issuer() {
     
     assert!(has_cert1);
     assert!(has_cert2);
+    
+    // Index slots whose certificate record is missing are skipped
+    env.as_contract(&contract.address, || {
+        env.storage().persistent().remove(&storage::DataKey::Certificate(cert_id1.clone()));
+    });
+    let page = contract.list_certificates_by_issuer(&admin, &0, &10);
+    assert_eq!(page.certificates.len(), 1);
+    assert_eq!(page.certificates.get(0).unwrap().id, cert_id2);
+    assert_eq!(contract.list_certificates_by_owner(&owner1, &0, &10).certificates.len(), 0);
+    
+    // A call returns at most `MAX_PAGE_SIZE` slots whatever the limit
+    env.as_contract(&contract.address, || {
+        let mut padding = Vec::new(&env);
+        for _ in 0..150 {
+            padding.push_back(cert_id2.clone());
+        }
+        storage::extend_index(&env, &storage::IndexScope::Issuer(admin.clone()), &padding);
+        storage::extend_index(&env, &storage::IndexScope::Owner(owner2.clone()), &padding);
+    });
+    let page = contract.list_certificates_by_issuer(&admin, &0, &1000);
+    assert_eq!(page.certificates.len(), 99);
+    assert_eq!(page.next_cursor, Some(query::MAX_PAGE_SIZE));
+    let page = contract.list_certificates_by_owner(&owner2, &0, &1000);
+    assert_eq!(page.certificates.len(), 100);
+    assert_eq!(page.next_cursor, Some(query::MAX_PAGE_SIZE));
+}
+
+#[test]
+fn test_certificate_indexes_are_paged() {
+    let env = create_test_env();
+    let (admin, contract) = setup_contract(&env);
+    allow_transfers(&env, &contract);
+    let owner = Address::generate(&env);
+    let other_owner = Address::generate(&env);
+    let signing_key = create_signing_key(&env, &contract, &admin, 1);
+    
+    env.mock_all_auths();
+    
+    // Issue more certificates than fit in one index page
+    let mut cert_ids = Vec::new(&env);
+    for i in 0..55 {
+        let mut metadata = create_test_metadata(&env);
+        metadata.title = String::from_str(&env, &alloc::format!("Certificate {}", i));
+        let signature = sign_certificate(&env, &signing_key, &admin, &owner, &metadata, None);
+        cert_ids.push_back(contract.issue_certificate(&admin, &owner, &cert_type_id(&env), &metadata, &metadata_root(&env, &metadata), &None, &vec![&env], &public_key(&env, &signing_key), &signature));
+    }
+    
+    // The index is split into fixed-size pages
+    env.as_contract(&contract.address, || {
+        let first_page: Vec<CertificateId> = env.storage().persistent().get(&storage::DataKey::IndexPage(storage::IndexScope::Owner(owner.clone()), 0)).unwrap();
+        let last_page: Vec<CertificateId> = env.storage().persistent().get(&storage::DataKey::IndexPage(storage::IndexScope::Owner(owner.clone()), 1)).unwrap();
+        assert_eq!(first_page.len(), storage::INDEX_PAGE_SIZE);
+        assert_eq!(last_page.len(), 5);
+    });
+    
+    // Following the cursors lists every certificate once, in issuance order
+    let mut listed = Vec::new(&env);
+    let mut cursor = Some(0);
+    while let Some(next) = cursor {
+        let page = contract.list_certificates_by_issuer(&admin, &next, &20);
+        for certificate in page.certificates.iter() {
+            listed.push_back(certificate.id);
+        }
+        cursor = page.next_cursor;
+    }
+    assert_eq!(listed, cert_ids);
+    assert_eq!(contract.count_certificates_by_issuer(&admin), 55);
+    
+    // A transferred certificate keeps its slot, so cursors stay valid, but is
+    // left out of its former owner's listing
+    let transferred = cert_ids.get(0).unwrap();
+    assert!(contract.transfer_certificate(&transferred, &other_owner));
+    let page = contract.list_certificates_by_owner(&owner, &0, &20);
+    assert_eq!(page.certificates.len(), 19);
+    assert_eq!(page.next_cursor, Some(20));
+    assert_eq!(contract.count_certificates_by_owner(&owner), 54);
+    assert_eq!(contract.list_certificates_by_owner(&other_owner, &0, &20).certificates.get(0).unwrap().id, transferred);
+    assert_eq!(contract.count_certificates_by_owner(&other_owner), 1);
+    
+    // Coming back, it takes its old slot again instead of a second one
+    assert!(contract.transfer_certificate(&transferred, &owner));
+    let page = contract.list_certificates_by_owner(&owner, &0, &20);
+    assert_eq!(page.certificates.get(0).unwrap().id, transferred);
+    assert_eq!(page.certificates.len(), 20);
+    assert_eq!(contract.list_certificates_by_owner(&owner, &40, &20).certificates.len(), 15);
+    assert_eq!(contract.count_certificates_by_owner(&owner), 55);
+    assert_eq!(contract.list_certificates_by_owner(&other_owner, &0, &20).certificates.len(), 0);
+    assert_eq!(contract.count_certificates_by_owner(&other_owner), 0);
+}
+
+// Helper to build a filter that matches every certificate
+fn any_certificate(env: &Env) -> CertificateFilter {
+    CertificateFilter {
+        statuses: vec![env],
+        cert_type_id: None,
+        achievement_type: None,
+        issued_from: None,
+        issued_to: None,
+        order: CertificateOrder::OldestFirst,
+    }
+}
+
+#[test]
+fn test_filter_certificates() {
+    let env = create_test_env();
+    let (admin, contract) = setup_contract(&env);
+    allow_transfers(&env, &contract);
+    let owner = Address::generate(&env);
+    let other_owner = Address::generate(&env);
+    let signing_key = create_signing_key(&env, &contract, &admin, 1);
+    
+    env.mock_all_auths();
+    
+    // Issue an audit and two course certificates a day apart
+    let mut cert_ids = Vec::new(&env);
+    for (i, achievement_type) in ["audit", "course_completion", "course_completion"].iter().enumerate() {
+        env.ledger().with_mut(|ledger| ledger.timestamp = 12345 + i as u64 * 86400);
+        let mut metadata = create_test_metadata(&env);
+        metadata.title = String::from_str(&env, &alloc::format!("Certificate {}", i));
+        metadata.achievement_type = String::from_str(&env, achievement_type);
+        let signature = sign_certificate(&env, &signing_key, &admin, &owner, &metadata, None);
+        cert_ids.push_back(contract.issue_certificate(&admin, &owner, &cert_type_id(&env), &metadata, &metadata_root(&env, &metadata), &None, &vec![&env], &public_key(&env, &signing_key), &signature));
+    }
+    let listed_ids = |page: CertificatePage| {
+        let mut ids = Vec::new(&env);
+        for certificate in page.certificates.iter() {
+            ids.push_back(certificate.id);
+        }
+        ids
+    };
+    
+    // Certification type and achievement type select a secondary index
+    let mut filter = any_certificate(&env);
+    filter.cert_type_id = Some(cert_type_id(&env));
+    assert_eq!(listed_ids(contract.filter_certificates_by_issuer(&admin, &filter, &None, &10)), cert_ids);
+    filter.cert_type_id = Some(String::from_str(&env, "CERT_TYPE_B"));
+    assert_eq!(contract.filter_certificates_by_issuer(&admin, &filter, &None, &10).certificates.len(), 0);
+    
+    let mut filter = any_certificate(&env);
+    filter.achievement_type = Some(String::from_str(&env, "course_completion"));
+    let page = contract.filter_certificates_by_owner(&owner, &filter, &None, &10);
+    assert_eq!(listed_ids(page), vec![&env, cert_ids.get(1).unwrap(), cert_ids.get(2).unwrap()]);
+    
+    // Newest first, one slot at a time, following the cursor down the index
+    filter.order = CertificateOrder::NewestFirst;
+    let page = contract.filter_certificates_by_owner(&owner, &filter, &None, &1);
+    assert_eq!(page.certificates.get(0).unwrap().id, cert_ids.get(2).unwrap());
+    assert_eq!(page.next_cursor, Some(0));
+    let page = contract.filter_certificates_by_owner(&owner, &filter, &page.next_cursor, &1);
+    assert_eq!(page.certificates.get(0).unwrap().id, cert_ids.get(1).unwrap());
+    assert_eq!(page.next_cursor, None);
+    
+    // Issuance date range
+    let mut filter = any_certificate(&env);
+    filter.issued_from = Some(12345 + 86400);
+    filter.issued_to = Some(12345 + 86400);
+    assert_eq!(listed_ids(contract.filter_certificates_by_issuer(&admin, &filter, &None, &10)), vec![&env, cert_ids.get(1).unwrap()]);
+    filter.issued_from = Some(12345 + 2 * 86400);
+    assert_eq!(contract.try_filter_certificates_by_issuer(&admin, &filter, &None, &10), Err(Ok(Error::InvalidInput)));
+    
+    // Status, resolved at the current ledger time
+    let note = String::from_str(&env, "Audit findings not closed");
+    contract.revoke_certificate(&admin, &cert_ids.get(0).unwrap(), &RevocationReason::NonConformity, &note, &None);
+    contract.suspend_certificate(&admin, &cert_ids.get(1).unwrap(), &note, &None);
+    let mut filter = any_certificate(&env);
+    filter.statuses = vec![&env, CertificateStatus::Active];
+    assert_eq!(listed_ids(contract.filter_certificates_by_issuer(&admin, &filter, &None, &10)), vec![&env, cert_ids.get(2).unwrap()]);
+    filter.statuses = vec![&env, CertificateStatus::Withdrawn, CertificateStatus::Suspended];
+    assert_eq!(listed_ids(contract.filter_certificates_by_issuer(&admin, &filter, &None, &10)), vec![&env, cert_ids.get(0).unwrap(), cert_ids.get(1).unwrap()]);
+    
+    // Transfers and amendments move a certificate between secondary indexes
+    let transferred = cert_ids.get(2).unwrap();
+    assert!(contract.transfer_certificate(&transferred, &other_owner));
+    let mut filter = any_certificate(&env);
+    filter.achievement_type = Some(String::from_str(&env, "course_completion"));
+    assert_eq!(listed_ids(contract.filter_certificates_by_owner(&other_owner, &filter, &None, &10)), vec![&env, transferred.clone()]);
+    assert_eq!(listed_ids(contract.filter_certificates_by_owner(&owner, &filter, &None, &10)), vec![&env, cert_ids.get(1).unwrap()]);
+    
+    let certificate = contract.get_certificate(&transferred);
+    let mut amended = certificate.metadata.clone();
+    amended.achievement_type = String::from_str(&env, "audit");
+    let amendment_signature = sign_amendment(&env, &signing_key, &certificate, &amended, 2);
+    contract.amend_certificate(&admin, &transferred, &amended, &metadata_root(&env, &amended), &note, &public_key(&env, &signing_key), &amendment_signature);
+    assert_eq!(contract.filter_certificates_by_owner(&other_owner, &filter, &None, &10).certificates.len(), 0);
+    filter.achievement_type = Some(String::from_str(&env, "audit"));
+    assert_eq!(listed_ids(contract.filter_certificates_by_issuer(&admin, &filter, &None, &10)), vec![&env, cert_ids.get(0).unwrap(), transferred]);
+    
+    // A call reads at most `MAX_FILTER_SLOTS` slots, so a page with no match can
+    // still have a next cursor
+    env.as_contract(&contract.address, || {
+        let mut padding = Vec::new(&env);
+        for _ in 0..150 {
+            padding.push_back(cert_ids.get(0).unwrap());
+        }
+        storage::extend_index(&env, &storage::IndexScope::Issuer(admin.clone()), &padding);
+    });
+    let mut filter = any_certificate(&env);
+    filter.statuses = vec![&env, CertificateStatus::Expired];
+    let page = contract.filter_certificates_by_issuer(&admin, &filter, &None, &1000);
+    assert_eq!(page.certificates.len(), 0);
+    assert_eq!(page.next_cursor, Some(query::MAX_FILTER_SLOTS));
+    let page = contract.filter_certificates_by_issuer(&admin, &filter, &page.next_cursor, &1000);
+    assert_eq!(page.next_cursor, None);
 }
 
 #[test]
@@ -252,28 +892,42 @@ fn test_add_and_remove_issuer() {
 #[test]
 fn test_revoke_certificate() {
     let env = create_test_env();
-    let (_admin, contract) = setup_contract(&env);
+    let (admin, contract) = setup_contract(&env);
     let owner = Address::generate(&env);
     
     // Create test metadata
     let metadata = create_test_metadata(&env);
-    let signature = Bytes::from_slice(&env, &[0u8; 64]);
+    let signing_key = create_signing_key(&env, &contract, &admin, 1);
     
     // Set the admin as the invoker for the issue_certificate call
     env.mock_all_auths();
     
     // Issue a certificate
-    let cert_id = contract.issue_certificate(&owner, &metadata, &None, &signature);
+    let signature = sign_certificate(&env, &signing_key, &admin, &owner, &metadata, None);
+    let cert_id = contract.issue_certificate(&admin, &owner, &cert_type_id(&env), &metadata, &metadata_root(&env, &metadata), &None, &vec![&env], &public_key(&env, &signing_key), &signature);
     
     // Set the admin as the invoker for the revoke_certificate call
     env.mock_all_auths();
     
     // Revoke the certificate
-    assert!(contract.revoke_certificate(&cert_id));
+    let note = String::from_str(&env, "Audit findings not closed");
+    assert!(contract.revoke_certificate(&admin, &cert_id, &RevocationReason::NonConformity, &note, &None));
     
-    // Verify the certificate is revoked
+    // Verify the certificate is withdrawn
     let certificate = contract.get_certificate(&cert_id);
-    assert!(certificate.revoked);
+    assert_eq!(certificate.status, CertificateStatus::Withdrawn);
+    
+    // Verify the revocation is recorded
+    let revocation = contract.get_revocation(&cert_id);
+    assert_eq!(revocation.reason, RevocationReason::NonConformity);
+    assert_eq!(revocation.note, note);
+    assert_eq!(revocation.revoked_by, admin);
+    assert_eq!(revocation.effective_date, 12345);
+    assert_eq!(revocation.recorded_at, 12345);
+    
+    // Revoking again changes nothing
+    assert!(!contract.revoke_certificate(&admin, &cert_id, &RevocationReason::Fraud, &note, &None));
+    assert_eq!(contract.get_revocation(&cert_id).reason, RevocationReason::NonConformity);
     
     // Verify the certificate is no longer valid
     assert!(!contract.verify_certificate_signature(&cert_id));
@@ -289,11 +943,22 @@ fn test_transfer_admin() {
     env.mock_all_auths();
     contract.add_issuer(&original_admin);
     
-    // Set the original admin as the invoker for the transfer_admin call
+    // Propose the new admin; the role stays with the original admin for now
     env.mock_all_auths();
+    assert!(contract.propose_admin(&new_admin, &None));
+    assert_eq!(contract.get_admin(), original_admin);
+    let proposal = contract.get_admin_proposal();
+    assert_eq!(proposal.proposed_admin, new_admin);
+    assert_eq!(proposal.expires_at, None);
     
-    // Transfer admin role
-    assert!(contract.transfer_admin(&new_admin));
+    // The new admin takes over by accepting, with their own authorization
+    assert!(contract.accept_admin());
+    assert_eq!(
+        env.auths().iter().map(|(address, _)| address.clone()).collect::<std::vec::Vec<_>>(),
+        std::vec![new_admin.clone()]
+    );
+    assert_eq!(contract.get_admin(), new_admin);
+    assert_eq!(contract.try_get_admin_proposal(), Err(Ok(Error::AdminProposalNotFound)));
     
     // Verify the new admin has admin privileges
     assert!(contract.is_issuer(&new_admin));
@@ -303,35 +968,1772 @@ fn test_transfer_admin() {
 }
 
 #[test]
-#[should_panic(expected = "Caller is not an authorized issuer or admin")]
+fn test_admin_proposal_cancellation_and_expiry() {
+    let env = create_test_env();
+    let (admin, contract) = setup_contract(&env);
+    let new_admin = Address::generate(&env);
+    env.mock_all_auths();
+    
+    // Proposals must name another address and expire in the future
+    assert_eq!(contract.try_propose_admin(&admin, &None), Err(Ok(Error::InvalidInput)));
+    assert_eq!(
+        contract.try_propose_admin(&new_admin, &Some(12345)),
+        Err(Ok(Error::InvalidExpirationDate))
+    );
+    assert_eq!(contract.try_accept_admin(), Err(Ok(Error::AdminProposalNotFound)));
+    assert_eq!(contract.try_cancel_admin_proposal(), Err(Ok(Error::AdminProposalNotFound)));
+    
+    // A cancelled proposal can no longer be accepted
+    assert!(contract.propose_admin(&new_admin, &None));
+    assert!(contract.cancel_admin_proposal());
+    assert_eq!(contract.try_accept_admin(), Err(Ok(Error::AdminProposalNotFound)));
+    
+    // A proposal to a mistyped address is fixed by proposing again
+    let mistyped = Address::generate(&env);
+    assert!(contract.propose_admin(&mistyped, &Some(20000)));
+    assert!(contract.propose_admin(&new_admin, &Some(20000)));
+    assert_eq!(contract.get_admin_proposal().proposed_admin, new_admin);
+    
+    // An expired proposal can no longer be accepted
+    env.ledger().with_mut(|ledger| ledger.timestamp = 20000);
+    assert_eq!(contract.try_accept_admin(), Err(Ok(Error::AdminProposalExpired)));
+    assert_eq!(contract.get_admin(), admin);
+    
+    // Only the admin can propose or cancel
+    env.set_auths(&[]);
+    assert!(contract.try_propose_admin(&new_admin, &None).is_err());
+    assert!(contract.try_cancel_admin_proposal().is_err());
+}
+
+#[test]
 fn test_unauthorized_issuance() {
     let env = create_test_env();
-    let (admin, _contract) = setup_contract(&env);
+    let (_admin, contract) = setup_contract(&env);
     let unauthorized = Address::generate(&env);
     let owner = Address::generate(&env);
     
-    // Create a new contract instance that's not initialized
-    // Note: register_contract is deprecated but we're using it for compatibility
-    // TODO: Update to use register when upgrading the SDK
-    #[allow(deprecated)]
-    let contract_id = env.register_contract(None, CertificationContract);
+    // Create test metadata
+    let metadata = create_test_metadata(&env);
+    let signature = BytesN::from_array(&env, &[0u8; 64]);
+    
+    // Try to issue a certificate as an address that was never added as an issuer
+    env.mock_all_auths();
+    let result = contract.try_issue_certificate(&unauthorized, &owner, &cert_type_id(&env), &metadata, &metadata_root(&env, &metadata), &None, &vec![&env], &BytesN::from_array(&env, &[0u8; 32]), &signature);
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+}
+
+#[test]
+fn test_added_issuer_can_issue_and_revoke() {
+    let env = create_test_env();
+    let (_admin, contract) = setup_contract(&env);
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
     
     // Create test metadata
     let metadata = create_test_metadata(&env);
-    let signature = Bytes::from_slice(&env, &[0u8; 64]);
     
-    // Initialize the contract with the admin
     env.mock_all_auths();
-    env.as_contract(&contract_id, || {
-        access_control::initialize(&env, &admin);
-        
-        // Try to issue a certificate as an unauthorized user
-        // This should panic with "Caller is not an authorized issuer or admin"
-        // We need to explicitly check that the caller is not an issuer
-        if !access_control::is_issuer(&env, unauthorized.clone()) {
-            panic!("Caller is not an authorized issuer or admin");
-        }
-        
-        issuance::issue_certificate(&env, owner, metadata, None, signature);
-    });
+    contract.add_issuer(&issuer);
+    let signing_key = create_signing_key(&env, &contract, &issuer, 2);
+    
+    // Issue a certificate as the added issuer
+    let signature = sign_certificate(&env, &signing_key, &issuer, &owner, &metadata, None);
+    let cert_id = contract.issue_certificate(&issuer, &owner, &cert_type_id(&env), &metadata, &metadata_root(&env, &metadata), &None, &vec![&env], &public_key(&env, &signing_key), &signature);
+    
+    // Only the issuer's own authorization is needed besides the owner's
+    assert_eq!(
+        env.auths().iter().map(|(address, _)| address.clone()).collect::<std::vec::Vec<_>>(),
+        std::vec![issuer.clone(), owner.clone()]
+    );
+    
+    // The issuer is recorded on the certificate and in the issuer index
+    let certificate = contract.get_certificate(&cert_id);
+    assert_eq!(certificate.issuer, issuer);
+    assert_eq!(contract.count_certificates_by_issuer(&issuer), 1);
+    
+    // The issuer can revoke its own certificate
+    let note = String::from_str(&env, "");
+    assert!(contract.revoke_certificate(&issuer, &cert_id, &RevocationReason::HolderRequest, &note, &None));
+    assert_eq!(contract.get_certificate(&cert_id).status, CertificateStatus::Withdrawn);
+}
+
+#[test]
+fn test_issuer_cannot_revoke_other_issuers_certificate() {
+    let env = create_test_env();
+    let (admin, contract) = setup_contract(&env);
+    let other_issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    
+    // Create test metadata
+    let metadata = create_test_metadata(&env);
+    let signing_key = create_signing_key(&env, &contract, &admin, 1);
+    
+    env.mock_all_auths();
+    contract.add_issuer(&other_issuer);
+    
+    // Issue a certificate as the admin and try to revoke it as another issuer
+    let signature = sign_certificate(&env, &signing_key, &admin, &owner, &metadata, None);
+    let cert_id = contract.issue_certificate(&admin, &owner, &cert_type_id(&env), &metadata, &metadata_root(&env, &metadata), &None, &vec![&env], &public_key(&env, &signing_key), &signature);
+    let note = String::from_str(&env, "");
+    let result = contract.try_revoke_certificate(
+        &other_issuer,
+        &cert_id,
+        &RevocationReason::Fraud,
+        &note,
+        &None,
+    );
+    assert_eq!(result, Err(Ok(Error::NotCertificateIssuer)));
+}
+
+#[test]
+fn test_removed_issuer_cannot_issue() {
+    let env = create_test_env();
+    let (_admin, contract) = setup_contract(&env);
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    
+    // Create test metadata
+    let metadata = create_test_metadata(&env);
+    let signature = BytesN::from_array(&env, &[0u8; 64]);
+    
+    env.mock_all_auths();
+    contract.add_issuer(&issuer);
+    contract.remove_issuer(&issuer);
+    
+    let result = contract.try_issue_certificate(&issuer, &owner, &cert_type_id(&env), &metadata, &metadata_root(&env, &metadata), &None, &vec![&env], &BytesN::from_array(&env, &[0u8; 32]), &signature);
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+}
+
+#[test]
+fn test_verify_certificate_signature() {
+    let env = create_test_env();
+    let (admin, contract) = setup_contract(&env);
+    let owner = Address::generate(&env);
+    let metadata = create_test_metadata(&env);
+    let signing_key = create_signing_key(&env, &contract, &admin, 1);
+    
+    // Issue a correctly signed certificate
+    let signature = sign_certificate(&env, &signing_key, &admin, &owner, &metadata, Some(20000));
+    let cert_id = contract.issue_certificate(&admin, &owner, &cert_type_id(&env), &metadata, &metadata_root(&env, &metadata), &Some(20000), &vec![&env], &public_key(&env, &signing_key), &signature);
+    
+    // The signature and signing key are stored on the certificate
+    let certificate = contract.get_certificate(&cert_id);
+    assert_eq!(certificate.signature, signature);
+    assert_eq!(certificate.signing_key.to_array(), signing_key.verifying_key().to_bytes());
+    assert!(contract.verify_certificate_signature(&cert_id));
+    
+    // The certificate stops verifying once it expires
+    env.ledger().with_mut(|ledger| ledger.timestamp = 20001);
+    assert!(!contract.verify_certificate_signature(&cert_id));
+}
+
+#[test]
+fn test_verify_certificate_with_mismatched_signature() {
+    let env = create_test_env();
+    let (admin, contract) = setup_contract(&env);
+    let owner = Address::generate(&env);
+    let metadata = create_test_metadata(&env);
+    let signing_key = create_signing_key(&env, &contract, &admin, 1);
+    
+    let signature = sign_certificate(&env, &signing_key, &admin, &owner, &metadata, Some(20000));
+    let cert_id = contract.issue_certificate(&admin, &owner, &cert_type_id(&env), &metadata, &metadata_root(&env, &metadata), &Some(20000), &vec![&env], &public_key(&env, &signing_key), &signature);
+    assert_eq!(contract.verify_certificate(&cert_id).status, VerificationStatus::Valid);
+    
+    // Store a signature by the same key over different content
+    let mut other_metadata = metadata.clone();
+    other_metadata.title = String::from_str(&env, "Forged Certificate");
+    let mut certificate = contract.get_certificate(&cert_id);
+    certificate.signature = sign_certificate(&env, &signing_key, &admin, &owner, &other_metadata, Some(20000));
+    env.as_contract(&contract.address, || storage::set_certificate(&env, &certificate));
+    
+    // The mismatch is reported instead of aborting the call
+    assert_eq!(contract.verify_certificate(&cert_id).status, VerificationStatus::BadSignature);
+    assert!(!contract.verify_certificate_signature(&cert_id));
+}
+
+#[test]
+fn test_issue_certificate_with_bad_signature() {
+    let env = create_test_env();
+    let (admin, contract) = setup_contract(&env);
+    let owner = Address::generate(&env);
+    let metadata = create_test_metadata(&env);
+    let signing_key = create_signing_key(&env, &contract, &admin, 1);
+    
+    // Sign different content than what is submitted
+    let mut other_metadata = metadata.clone();
+    other_metadata.title = String::from_str(&env, "Forged Certificate");
+    let signature = sign_certificate(&env, &signing_key, &admin, &owner, &other_metadata, None);
+    
+    let result = contract.try_issue_certificate(&admin, &owner, &cert_type_id(&env), &metadata, &metadata_root(&env, &metadata), &None, &vec![&env], &public_key(&env, &signing_key), &signature);
+    assert_eq!(result, Err(Ok(Error::BadSignature)));
+    assert_eq!(contract.get_certificate_count(), 0);
+}
+
+#[test]
+fn test_issue_certificate_without_signing_key() {
+    let env = create_test_env();
+    let (admin, contract) = setup_contract(&env);
+    let owner = Address::generate(&env);
+    let metadata = create_test_metadata(&env);
+    accredit_issuer(&env, &contract, &admin);
+    
+    let signature = BytesN::from_array(&env, &[0u8; 64]);
+    let result = contract.try_issue_certificate(&admin, &owner, &cert_type_id(&env), &metadata, &metadata_root(&env, &metadata), &None, &vec![&env], &BytesN::from_array(&env, &[0u8; 32]), &signature);
+    assert_eq!(result, Err(Ok(Error::SigningKeyNotFound)));
+}
+
+#[test]
+fn test_signing_key_validity_window() {
+    let env = create_test_env();
+    let (admin, contract) = setup_contract(&env);
+    let owner = Address::generate(&env);
+    let metadata = create_test_metadata(&env);
+    accredit_issuer(&env, &contract, &admin);
+    
+    // Register a key that is only valid until just after the current time
+    let signing_key = Ed25519Key::from_bytes(&[3u8; 32]);
+    let public_key = BytesN::from_array(&env, &signing_key.verifying_key().to_bytes());
+    env.mock_all_auths();
+    assert!(contract.register_signing_key(&admin, &public_key, &0, &Some(12346)));
+    assert!(!contract.register_signing_key(&admin, &public_key, &0, &None));
+    
+    let signature = sign_certificate(&env, &signing_key, &admin, &owner, &metadata, None);
+    let cert_id = contract.issue_certificate(&admin, &owner, &cert_type_id(&env), &metadata, &metadata_root(&env, &metadata), &None, &vec![&env], &public_key, &signature);
+    
+    // Certificates signed inside the window keep verifying after it closes
+    env.ledger().with_mut(|ledger| ledger.timestamp = 20000);
+    assert!(contract.verify_certificate_signature(&cert_id));
+    
+    // Revoking the key invalidates everything it signed
+    assert!(contract.revoke_signing_key(&admin, &public_key));
+    assert!(!contract.verify_certificate_signature(&cert_id));
+    assert!(contract.get_signing_keys(&admin).get(0).unwrap().revoked);
+}
+
+#[test]
+fn test_expired_signing_key_cannot_issue() {
+    let env = create_test_env();
+    let (admin, contract) = setup_contract(&env);
+    let owner = Address::generate(&env);
+    let metadata = create_test_metadata(&env);
+    accredit_issuer(&env, &contract, &admin);
+    
+    // Register a key whose window closed before the current time
+    let signing_key = Ed25519Key::from_bytes(&[3u8; 32]);
+    let public_key = BytesN::from_array(&env, &signing_key.verifying_key().to_bytes());
+    env.mock_all_auths();
+    contract.register_signing_key(&admin, &public_key, &0, &Some(12000));
+    
+    let signature = sign_certificate(&env, &signing_key, &admin, &owner, &metadata, None);
+    let result = contract.try_issue_certificate(&admin, &owner, &cert_type_id(&env), &metadata, &metadata_root(&env, &metadata), &None, &vec![&env], &public_key, &signature);
+    assert_eq!(result, Err(Ok(Error::NoActiveSigningKey)));
+}
+
+#[test]
+fn test_overlapping_signing_keys_can_both_issue() {
+    let env = create_test_env();
+    let (admin, contract) = setup_contract(&env);
+    let first_owner = Address::generate(&env);
+    let second_owner = Address::generate(&env);
+    let metadata = create_test_metadata(&env);
+    
+    // Rotate to a newer key while the older one is still inside its window
+    let old_key = create_signing_key(&env, &contract, &admin, 1);
+    let new_key = Ed25519Key::from_bytes(&[4u8; 32]);
+    contract.register_signing_key(&admin, &public_key(&env, &new_key), &12000, &None);
+    
+    // Certificates signed with either key are accepted and verify
+    let signature = sign_certificate(&env, &old_key, &admin, &first_owner, &metadata, None);
+    let old_cert_id = contract.issue_certificate(&admin, &first_owner, &cert_type_id(&env), &metadata, &metadata_root(&env, &metadata), &None, &vec![&env], &public_key(&env, &old_key), &signature);
+    let signature = sign_certificate(&env, &new_key, &admin, &second_owner, &metadata, None);
+    let new_cert_id = contract.issue_certificate(&admin, &second_owner, &cert_type_id(&env), &metadata, &metadata_root(&env, &metadata), &None, &vec![&env], &public_key(&env, &new_key), &signature);
+    assert!(contract.verify_certificate_signature(&old_cert_id));
+    assert!(contract.verify_certificate_signature(&new_cert_id));
+    
+    // A signature made with one key does not pass as the other's
+    let owner = Address::generate(&env);
+    let signature = sign_certificate(&env, &old_key, &admin, &owner, &metadata, None);
+    let result = contract.try_issue_certificate(&admin, &owner, &cert_type_id(&env), &metadata, &metadata_root(&env, &metadata), &None, &vec![&env], &public_key(&env, &new_key), &signature);
+    assert_eq!(result, Err(Ok(Error::BadSignature)));
+}
+
+#[test]
+fn test_verify_certificate_report() {
+    let env = create_test_env();
+    let (admin, contract) = setup_contract(&env);
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let metadata = create_test_metadata(&env);
+    
+    env.mock_all_auths();
+    contract.add_issuer(&issuer);
+    let signing_key = create_signing_key(&env, &contract, &issuer, 2);
+    
+    // A valid certificate reports its issuer, key and expiry
+    let signature = sign_certificate(&env, &signing_key, &issuer, &owner, &metadata, Some(20000));
+    let cert_id = contract.issue_certificate(&issuer, &owner, &cert_type_id(&env), &metadata, &metadata_root(&env, &metadata), &Some(20000), &vec![&env], &public_key(&env, &signing_key), &signature);
+    let report = contract.verify_certificate(&cert_id);
+    assert_eq!(report.status, VerificationStatus::Valid);
+    assert_eq!(report.checked_at, 12345);
+    assert_eq!(report.issuer, Some(issuer.clone()));
+    assert!(report.issuer_active);
+    assert_eq!(report.signing_key.to_array(), signing_key.verifying_key().to_bytes());
+    assert_eq!(report.issuance_date, Some(12345));
+    assert_eq!(report.expiration_date, Some(20000));
+    
+    // A revoked signing key is reported as a bad signature
+    contract.revoke_signing_key(&issuer, &public_key(&env, &signing_key));
+    assert_eq!(contract.verify_certificate(&cert_id).status, VerificationStatus::BadSignature);
+    
+    // An issuer that was removed is reported as inactive
+    contract.remove_issuer(&issuer);
+    let report = contract.verify_certificate(&cert_id);
+    assert_eq!(report.status, VerificationStatus::IssuerInactive);
+    assert!(!report.issuer_active);
+    
+    // Expiry is reported once the expiration date has passed
+    env.ledger().with_mut(|ledger| ledger.timestamp = 20001);
+    let report = contract.verify_certificate(&cert_id);
+    assert_eq!(report.status, VerificationStatus::Expired);
+    assert_eq!(report.checked_at, 20001);
+    
+    // Revocation takes precedence over every other reason
+    let admin_key = create_signing_key(&env, &contract, &admin, 1);
+    let signature = sign_certificate(&env, &admin_key, &admin, &owner, &metadata, None);
+    let admin_cert_id = contract.issue_certificate(&admin, &owner, &cert_type_id(&env), &metadata, &metadata_root(&env, &metadata), &None, &vec![&env], &public_key(&env, &admin_key), &signature);
+    contract.revoke_certificate(
+        &admin,
+        &admin_cert_id,
+        &RevocationReason::Superseded,
+        &String::from_str(&env, ""),
+        &None,
+    );
+    assert_eq!(contract.verify_certificate(&admin_cert_id).status, VerificationStatus::Revoked);
+}
+
+#[test]
+fn test_verify_unknown_certificate() {
+    let env = create_test_env();
+    let (_admin, contract) = setup_contract(&env);
+    let cert_id = CertificateId(BytesN::from_array(&env, &[9u8; 32]));
+    
+    let report = contract.verify_certificate(&cert_id);
+    assert_eq!(report.status, VerificationStatus::NotFound);
+    assert_eq!(report.certificate_id, cert_id);
+    assert_eq!(report.issuer, None);
+    assert!(!contract.verify_certificate_signature(&cert_id));
+}
+
+// Helper function to encode the QR verification payload of a certificate
+fn encode_qr_payload(env: &Env, contract: &CertificationContractClient, cert_id: &CertificateId) -> Bytes {
+    let address = contract.address.to_string();
+    let mut strkey = [0u8; 56];
+    address.copy_into_slice(&mut strkey);
+    
+    let payload = certificate_qr::Payload::new(
+        cert_id.0.to_array(),
+        core::str::from_utf8(&strkey).unwrap(),
+        TEST_NETWORK_PASSPHRASE,
+    )
+    .unwrap();
+    
+    // Scanners decode the base45 text printed in the QR code
+    let decoded = certificate_qr::Payload::decode(&payload.encode()).unwrap();
+    Bytes::from_slice(env, &decoded.to_bytes())
+}
+
+const TEST_NETWORK_PASSPHRASE: &str = "Test SDF Network ; September 2015";
+
+#[test]
+fn test_verify_qr_payload() {
+    let env = create_test_env();
+    env.ledger().with_mut(|ledger| {
+        ledger.network_id = certificate_qr::network_id(TEST_NETWORK_PASSPHRASE);
+    });
+    let (admin, contract) = setup_contract(&env);
+    let owner = Address::generate(&env);
+    let metadata = create_test_metadata(&env);
+    let signing_key = create_signing_key(&env, &contract, &admin, 1);
+    
+    env.mock_all_auths();
+    let signature = sign_certificate(&env, &signing_key, &admin, &owner, &metadata, None);
+    let cert_id = contract.issue_certificate(&admin, &owner, &cert_type_id(&env), &metadata, &metadata_root(&env, &metadata), &None, &vec![&env], &public_key(&env, &signing_key), &signature);
+    
+    // A payload for this contract and network verifies the certificate in one call
+    let payload = encode_qr_payload(&env, &contract, &cert_id);
+    let report = contract.verify_qr_payload(&payload);
+    assert_eq!(report.certificate_id, cert_id);
+    assert_eq!(report.status, VerificationStatus::Valid);
+    
+    // Payloads that are corrupted or of an unknown version are rejected
+    let mut corrupted = payload.clone();
+    corrupted.set(10, corrupted.get(10).unwrap() ^ 1);
+    assert_eq!(contract.try_verify_qr_payload(&corrupted), Err(Ok(Error::InvalidPayload)));
+    assert_eq!(contract.try_verify_qr_payload(&payload.slice(..100)), Err(Ok(Error::InvalidPayload)));
+    let mut future = payload.clone();
+    future.set(0, 2);
+    assert_eq!(contract.try_verify_qr_payload(&future), Err(Ok(Error::UnsupportedPayloadVersion)));
+    
+    // A payload made for another deployment is rejected
+    let other = CertificationContractClient::new(&env, &env.register(CertificationContract, ()));
+    assert_eq!(contract.try_verify_qr_payload(&encode_qr_payload(&env, &other, &cert_id)), Err(Ok(Error::PayloadContractMismatch)));
+    
+    // So is a payload made for another network
+    env.ledger().with_mut(|ledger| {
+        ledger.network_id = certificate_qr::network_id("Public Global Stellar Network ; September 2015");
+    });
+    assert_eq!(contract.try_verify_qr_payload(&payload), Err(Ok(Error::PayloadNetworkMismatch)));
+}
+
+#[test]
+fn test_typed_errors() {
+    let env = create_test_env();
+    let (admin, contract) = setup_contract(&env);
+    let owner = Address::generate(&env);
+    let metadata = create_test_metadata(&env);
+    
+    env.mock_all_auths();
+    
+    // Initializing twice is rejected
+    assert_eq!(contract.try_initialize(&admin), Err(Ok(Error::AlreadyInitialized)));
+    
+    // Unknown certificates are reported as such
+    let unknown_id = CertificateId(BytesN::from_array(&env, &[7u8; 32]));
+    assert_eq!(contract.try_get_certificate(&unknown_id), Err(Ok(Error::CertificateNotFound)));
+    assert_eq!(
+        contract.try_transfer_certificate(&unknown_id, &owner),
+        Err(Ok(Error::CertificateNotFound))
+    );
+    assert_eq!(
+        contract.try_revoke_certificate(
+            &admin,
+            &unknown_id,
+            &RevocationReason::Fraud,
+            &String::from_str(&env, ""),
+            &None,
+        ),
+        Err(Ok(Error::CertificateNotFound))
+    );
+    
+    // Batch inputs must line up
+    let result = contract.try_batch_issue_certificates(
+        &admin,
+        &vec![&env, owner.clone()],
+        &vec![&env, cert_type_id(&env)],
+        &vec![&env, metadata],
+        &vec![&env],
+        &vec![&env],
+        &vec![&env],
+        &vec![&env],
+        &vec![&env],
+        &BatchMode::AllOrNothing,
+    );
+    assert_eq!(result, Err(Ok(Error::InputLengthMismatch)));
+    
+    // Signing key registration is validated
+    let zero_key = BytesN::from_array(&env, &[0u8; 32]);
+    let public_key = BytesN::from_array(&env, &[5u8; 32]);
+    assert_eq!(
+        contract.try_register_signing_key(&admin, &zero_key, &0, &None),
+        Err(Ok(Error::InvalidSigningKey))
+    );
+    assert_eq!(
+        contract.try_register_signing_key(&admin, &public_key, &100, &Some(100)),
+        Err(Ok(Error::InvalidValidityWindow))
+    );
+    assert_eq!(
+        contract.try_revoke_signing_key(&admin, &public_key),
+        Err(Ok(Error::SigningKeyNotFound))
+    );
+}
+
+#[test]
+fn test_uninitialized_contract_errors() {
+    let env = create_test_env();
+    #[allow(deprecated)]
+    let contract_id = env.register_contract(None, CertificationContract);
+    let contract = CertificationContractClient::new(&env, &contract_id);
+    
+    env.mock_all_auths();
+    assert_eq!(contract.try_get_admin(), Err(Ok(Error::NotInitialized)));
+    assert_eq!(
+        contract.try_add_issuer(&Address::generate(&env)),
+        Err(Ok(Error::NotInitialized))
+    );
+}
+
+#[test]
+fn test_list_revocations() {
+    let env = create_test_env();
+    let (admin, contract) = setup_contract(&env);
+    let metadata = create_test_metadata(&env);
+    let signing_key = create_signing_key(&env, &contract, &admin, 1);
+    let note = String::from_str(&env, "");
+    
+    env.mock_all_auths();
+    
+    // Issue three certificates
+    let mut cert_ids = std::vec::Vec::new();
+    for _ in 0..3 {
+        let owner = Address::generate(&env);
+        let signature = sign_certificate(&env, &signing_key, &admin, &owner, &metadata, None);
+        cert_ids.push(contract.issue_certificate(&admin, &owner, &cert_type_id(&env), &metadata, &metadata_root(&env, &metadata), &None, &vec![&env], &public_key(&env, &signing_key), &signature));
+    }
+    
+    // A revocation cannot take effect in the future
+    let result = contract.try_revoke_certificate(
+        &admin,
+        &cert_ids[0],
+        &RevocationReason::Fraud,
+        &note,
+        &Some(13000),
+    );
+    assert_eq!(result, Err(Ok(Error::InvalidEffectiveDate)));
+    
+    // Revoke the certificates at different times, backdating the second one
+    env.ledger().with_mut(|ledger| ledger.timestamp = 13000);
+    contract.revoke_certificate(&admin, &cert_ids[0], &RevocationReason::Fraud, &note, &None);
+    env.ledger().with_mut(|ledger| ledger.timestamp = 14000);
+    contract.revoke_certificate(&admin, &cert_ids[1], &RevocationReason::Superseded, &note, &Some(12500));
+    env.ledger().with_mut(|ledger| ledger.timestamp = 15000);
+    contract.revoke_certificate(&admin, &cert_ids[2], &RevocationReason::HolderRequest, &note, &None);
+    
+    // Page through the whole registry
+    let page = contract.list_revocations(&0, &0, &2);
+    assert_eq!(page.revocations.len(), 2);
+    assert_eq!(page.revocations.get(0).unwrap().certificate_id, cert_ids[0]);
+    assert_eq!(page.revocations.get(1).unwrap().effective_date, 12500);
+    assert_eq!(page.next_cursor, Some(2));
+    
+    let page = contract.list_revocations(&0, &2, &2);
+    assert_eq!(page.revocations.len(), 1);
+    assert_eq!(page.revocations.get(0).unwrap().certificate_id, cert_ids[2]);
+    assert_eq!(page.next_cursor, None);
+    
+    // Sync from a point in time: selection is by the time the revocation was
+    // recorded, so the backdated revocation is still picked up
+    let page = contract.list_revocations(&13500, &0, &10);
+    assert_eq!(page.revocations.len(), 2);
+    assert_eq!(page.revocations.get(0).unwrap().certificate_id, cert_ids[1]);
+    assert_eq!(page.revocations.get(1).unwrap().recorded_at, 15000);
+    
+    // Nothing recorded after the last revocation
+    let page = contract.list_revocations(&15001, &0, &10);
+    assert_eq!(page.revocations.len(), 0);
+    assert_eq!(page.next_cursor, None);
+    
+    // Certificates that were never revoked have no registry entry
+    let owner = Address::generate(&env);
+    let signature = sign_certificate(&env, &signing_key, &admin, &owner, &metadata, None);
+    let cert_id = contract.issue_certificate(&admin, &owner, &cert_type_id(&env), &metadata, &metadata_root(&env, &metadata), &None, &vec![&env], &public_key(&env, &signing_key), &signature);
+    assert_eq!(contract.try_get_revocation(&cert_id), Err(Ok(Error::RevocationNotFound)));
+    
+    // A call returns at most `MAX_PAGE_SIZE` revocations whatever the limit
+    env.as_contract(&contract.address, || {
+        let revocation = storage::get_revocation(&env, 2).unwrap();
+        for _ in 0..150 {
+            storage::add_revocation(&env, &revocation);
+        }
+    });
+    let page = contract.list_revocations(&0, &0, &1000);
+    assert_eq!(page.revocations.len(), query::MAX_PAGE_SIZE);
+    assert_eq!(page.next_cursor, Some(query::MAX_PAGE_SIZE));
+}
+
+#[test]
+fn test_suspend_and_reinstate_certificate() {
+    let env = create_test_env();
+    let (admin, contract) = setup_contract(&env);
+    allow_transfers(&env, &contract);
+    let owner = Address::generate(&env);
+    let new_owner = Address::generate(&env);
+    let metadata = create_test_metadata(&env);
+    let signing_key = create_signing_key(&env, &contract, &admin, 1);
+    let reason = String::from_str(&env, "Surveillance audit overdue");
+    
+    env.mock_all_auths();
+    let signature = sign_certificate(&env, &signing_key, &admin, &owner, &metadata, None);
+    let cert_id = contract.issue_certificate(&admin, &owner, &cert_type_id(&env), &metadata, &metadata_root(&env, &metadata), &None, &vec![&env], &public_key(&env, &signing_key), &signature);
+    
+    // Suspend the certificate indefinitely
+    assert!(contract.suspend_certificate(&admin, &cert_id, &reason, &None));
+    assert!(!contract.suspend_certificate(&admin, &cert_id, &reason, &None));
+    assert_eq!(contract.get_certificate_status(&cert_id), CertificateStatus::Suspended);
+    assert_eq!(contract.get_suspension(&cert_id).reason, reason);
+    
+    // A suspended certificate neither verifies nor transfers, but is still listed
+    assert_eq!(contract.verify_certificate(&cert_id).status, VerificationStatus::Suspended);
+    assert!(!contract.transfer_certificate(&cert_id, &new_owner));
+    let certificates = contract.list_certificates_by_owner(&owner, &0, &10).certificates;
+    assert_eq!(certificates.get(0).unwrap().status, CertificateStatus::Suspended);
+    
+    // Reinstating restores it
+    assert!(contract.reinstate_certificate(&admin, &cert_id));
+    assert!(!contract.reinstate_certificate(&admin, &cert_id));
+    assert_eq!(contract.verify_certificate(&cert_id).status, VerificationStatus::Valid);
+    
+    // A suspension with a lift date ends on its own
+    assert_eq!(
+        contract.try_suspend_certificate(&admin, &cert_id, &reason, &Some(12345)),
+        Err(Ok(Error::InvalidLiftDate))
+    );
+    assert!(contract.suspend_certificate(&admin, &cert_id, &reason, &Some(13000)));
+    assert_eq!(contract.get_certificate_status(&cert_id), CertificateStatus::Suspended);
+    env.ledger().with_mut(|ledger| ledger.timestamp = 13000);
+    assert_eq!(contract.get_certificate_status(&cert_id), CertificateStatus::Active);
+    assert!(contract.transfer_certificate(&cert_id, &new_owner));
+    assert_eq!(contract.get_certificate(&cert_id).owner, new_owner);
+    
+    // Withdrawn certificates cannot be suspended
+    contract.revoke_certificate(&admin, &cert_id, &RevocationReason::NonConformity, &reason, &None);
+    assert_eq!(
+        contract.try_suspend_certificate(&admin, &cert_id, &reason, &None),
+        Err(Ok(Error::CertificateWithdrawn))
+    );
+}
+
+#[test]
+fn test_expired_certificate_status() {
+    let env = create_test_env();
+    let (admin, contract) = setup_contract(&env);
+    let owner = Address::generate(&env);
+    let metadata = create_test_metadata(&env);
+    let signing_key = create_signing_key(&env, &contract, &admin, 1);
+    
+    env.mock_all_auths();
+    let signature = sign_certificate(&env, &signing_key, &admin, &owner, &metadata, Some(20000));
+    let cert_id = contract.issue_certificate(&admin, &owner, &cert_type_id(&env), &metadata, &metadata_root(&env, &metadata), &Some(20000), &vec![&env], &public_key(&env, &signing_key), &signature);
+    assert!(contract.suspend_certificate(&admin, &cert_id, &String::from_str(&env, ""), &None));
+    
+    // Expiry takes over from a suspension
+    env.ledger().with_mut(|ledger| ledger.timestamp = 20001);
+    assert_eq!(contract.get_certificate(&cert_id).status, CertificateStatus::Expired);
+    assert_eq!(contract.verify_certificate(&cert_id).status, VerificationStatus::Expired);
+    assert!(!contract.reinstate_certificate(&admin, &cert_id));
+    assert!(!contract.transfer_certificate(&cert_id, &Address::generate(&env)));
+}
+
+#[test]
+fn test_renew_certificate() {
+    let env = create_test_env();
+    let (admin, contract) = setup_contract(&env);
+    let owner = Address::generate(&env);
+    let metadata = create_test_metadata(&env);
+    let signing_key = create_signing_key(&env, &contract, &admin, 1);
+    
+    env.mock_all_auths();
+    let signature = sign_certificate(&env, &signing_key, &admin, &owner, &metadata, Some(20000));
+    let first_id = contract.issue_certificate(&admin, &owner, &cert_type_id(&env), &metadata, &metadata_root(&env, &metadata), &Some(20000), &vec![&env], &public_key(&env, &signing_key), &signature);
+    
+    // The new expiration date must be in the future
+    let first = contract.get_certificate(&first_id);
+    let signature = sign_renewal(&env, &signing_key, &first, Some(12000));
+    assert_eq!(
+        contract.try_renew_certificate(&first_id, &Some(12000), &public_key(&env, &signing_key), &signature),
+        Err(Ok(Error::InvalidExpirationDate))
+    );
+    
+    // Renew after the certificate expired
+    env.ledger().with_mut(|ledger| ledger.timestamp = 20500);
+    let signature = sign_renewal(&env, &signing_key, &first, Some(30000));
+    let second_id = contract.renew_certificate(&first_id, &Some(30000), &public_key(&env, &signing_key), &signature);
+    
+    // The successor keeps the owner and metadata and verifies
+    let second = contract.get_certificate(&second_id);
+    assert_eq!(second.owner, owner);
+    assert_eq!(second.metadata, metadata);
+    assert_eq!(second.issuance_date, 20500);
+    assert_eq!(second.expiration_date, Some(30000));
+    assert_eq!(contract.verify_certificate(&second_id).status, VerificationStatus::Valid);
+    
+    // The original is superseded and cannot be renewed again
+    assert_eq!(contract.get_certificate_status(&first_id), CertificateStatus::Superseded);
+    assert_eq!(contract.verify_certificate(&first_id).status, VerificationStatus::Superseded);
+    assert_eq!(
+        contract.try_renew_certificate(&first_id, &Some(30000), &public_key(&env, &signing_key), &signature),
+        Err(Ok(Error::CertificateSuperseded))
+    );
+    
+    // Renew the successor too
+    env.ledger().with_mut(|ledger| ledger.timestamp = 29000);
+    let signature = sign_renewal(&env, &signing_key, &second, Some(40000));
+    let third_id = contract.renew_certificate(&second_id, &Some(40000), &public_key(&env, &signing_key), &signature);
+    
+    // Walk the chain in both directions
+    assert_eq!(contract.get_predecessor(&first_id), None);
+    assert_eq!(contract.get_predecessor(&third_id), Some(second_id.clone()));
+    assert_eq!(contract.get_successor(&first_id), Some(second_id.clone()));
+    assert_eq!(contract.get_successor(&third_id), None);
+    let chain = vec![&env, first_id.clone(), second_id.clone(), third_id.clone()];
+    assert_eq!(contract.get_renewal_chain(&first_id), chain);
+    assert_eq!(contract.get_renewal_chain(&second_id), chain);
+    assert_eq!(contract.get_renewal_chain(&third_id), chain);
+    assert_eq!(contract.count_certificates_by_owner(&owner), 3);
+}
+
+#[test]
+fn test_renewal_checks_certification_type() {
+    let env = create_test_env();
+    let (admin, contract) = setup_contract(&env);
+    let owner = Address::generate(&env);
+    let metadata = create_test_metadata(&env);
+    let signing_key = create_signing_key(&env, &contract, &admin, 1);
+    let registry = CertificationTypeManagementContractClient::new(&env, &contract.get_certification_type_registry());
+    
+    env.mock_all_auths();
+    let signature = sign_certificate(&env, &signing_key, &admin, &owner, &metadata, Some(20000));
+    let first_id = contract.issue_certificate(&admin, &owner, &cert_type_id(&env), &metadata, &metadata_root(&env, &metadata), &Some(20000), &vec![&env], &public_key(&env, &signing_key), &signature);
+    let first = contract.get_certificate(&first_id);
+    
+    // Without a new expiration date, the type's validity period of 3 years applies
+    env.ledger().with_mut(|ledger| ledger.timestamp = 19000);
+    let signature = sign_renewal(&env, &signing_key, &first, None);
+    let second_id = contract.renew_certificate(&first_id, &None, &public_key(&env, &signing_key), &signature);
+    assert_eq!(contract.get_certificate(&second_id).expiration_date, Some(19000 + 3 * 365 * 86_400));
+    
+    // The signature covers the expiration date as submitted
+    assert_eq!(contract.verify_certificate(&second_id).status, VerificationStatus::Valid);
+    assert_eq!(contract.get_certificate_version(&second_id, &1).expiration_date, None);
+    
+    // Certificates of a deprecated type can no longer be renewed
+    registry.deprecate_certification_type(&cert_type_id(&env), &String::from_str(&env, "Replaced"));
+    let second = contract.get_certificate(&second_id);
+    let signature = sign_renewal(&env, &signing_key, &second, Some(40000));
+    assert_eq!(
+        contract.try_renew_certificate(&second_id, &Some(40000), &public_key(&env, &signing_key), &signature),
+        Err(Ok(Error::CertificationTypeInactive))
+    );
+}
+
+#[test]
+fn test_renewal_signature_covers_predecessor() {
+    let env = create_test_env();
+    let (admin, contract) = setup_contract(&env);
+    let owner = Address::generate(&env);
+    let metadata = create_test_metadata(&env);
+    let signing_key = create_signing_key(&env, &contract, &admin, 1);
+    
+    env.mock_all_auths();
+    let signature = sign_certificate(&env, &signing_key, &admin, &owner, &metadata, Some(20000));
+    let cert_id = contract.issue_certificate(&admin, &owner, &cert_type_id(&env), &metadata, &metadata_root(&env, &metadata), &Some(20000), &vec![&env], &public_key(&env, &signing_key), &signature);
+    
+    // A plain issuance signature cannot be replayed as a renewal
+    let signature = sign_certificate(&env, &signing_key, &admin, &owner, &metadata, Some(30000));
+    assert_eq!(
+        contract.try_renew_certificate(&cert_id, &Some(30000), &public_key(&env, &signing_key), &signature),
+        Err(Ok(Error::BadSignature))
+    );
+    assert_eq!(contract.get_certificate(&cert_id).status, CertificateStatus::Active);
+}
+
+#[test]
+fn test_amend_certificate() {
+    let env = create_test_env();
+    let (admin, contract) = setup_contract(&env);
+    let owner = Address::generate(&env);
+    let metadata = create_test_metadata(&env);
+    let signing_key = create_signing_key(&env, &contract, &admin, 1);
+    
+    env.mock_all_auths();
+    let signature = sign_certificate(&env, &signing_key, &admin, &owner, &metadata, None);
+    let cert_id = contract.issue_certificate(&admin, &owner, &cert_type_id(&env), &metadata, &metadata_root(&env, &metadata), &None, &vec![&env], &public_key(&env, &signing_key), &signature);
+    
+    // A certificate that was never amended has its issued content as version 1
+    assert_eq!(contract.get_certificate_history(&cert_id).len(), 1);
+    assert_eq!(contract.get_certificate_version(&cert_id, &1).signature, signature);
+    assert_eq!(contract.try_get_certificate_version(&cert_id, &2), Err(Ok(Error::VersionNotFound)));
+    
+    // Extend the scope
+    env.ledger().with_mut(|ledger| ledger.timestamp = 13000);
+    let certificate = contract.get_certificate(&cert_id);
+    let mut extended = metadata.clone();
+    extended.description = String::from_str(&env, "Scope extended to a second production site");
+    let amendment_signature = sign_amendment(&env, &signing_key, &certificate, &extended, 2);
+    let note = String::from_str(&env, "Scope extension");
+    assert_eq!(
+        contract.amend_certificate(&admin, &cert_id, &extended, &metadata_root(&env, &extended), &note, &public_key(&env, &signing_key), &amendment_signature),
+        2
+    );
+    
+    // The certificate shows the new content and still verifies
+    let certificate = contract.get_certificate(&cert_id);
+    assert_eq!(certificate.metadata, extended);
+    assert_eq!(certificate.signature, amendment_signature);
+    assert_eq!(certificate.issuance_date, 12345);
+    assert!(contract.verify_certificate_signature(&cert_id));
+    
+    // Both versions are kept
+    let history = contract.get_certificate_history(&cert_id);
+    assert_eq!(history.len(), 2);
+    let first = history.get(0).unwrap();
+    assert_eq!(first.metadata, metadata);
+    assert_eq!(first.signature, signature);
+    assert_eq!(first.recorded_at, 12345);
+    let second = contract.get_certificate_version(&cert_id, &2);
+    assert_eq!(second.metadata, extended);
+    assert_eq!(second.recorded_at, 13000);
+    assert_eq!(second.recorded_by, admin);
+    assert_eq!(second.note, note);
+    assert_ne!(first.content_hash, second.content_hash);
+    
+    // Withdrawn certificates cannot be amended
+    contract.revoke_certificate(&admin, &cert_id, &RevocationReason::Fraud, &note, &None);
+    let amendment_signature = sign_amendment(&env, &signing_key, &certificate, &metadata, 3);
+    assert_eq!(
+        contract.try_amend_certificate(&admin, &cert_id, &metadata, &metadata_root(&env, &metadata), &note, &public_key(&env, &signing_key), &amendment_signature),
+        Err(Ok(Error::CertificateWithdrawn))
+    );
+}
+
+#[test]
+fn test_admin_amendment_verifies() {
+    let env = create_test_env();
+    let (admin, contract) = setup_contract(&env);
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let metadata = create_test_metadata(&env);
+    
+    env.mock_all_auths();
+    contract.add_issuer(&issuer);
+    let issuer_key = create_signing_key(&env, &contract, &issuer, 2);
+    let admin_key = create_signing_key(&env, &contract, &admin, 1);
+    let signature = sign_certificate(&env, &issuer_key, &issuer, &owner, &metadata, None);
+    let cert_id = contract.issue_certificate(&issuer, &owner, &cert_type_id(&env), &metadata, &metadata_root(&env, &metadata), &None, &vec![&env], &public_key(&env, &issuer_key), &signature);
+    
+    // The admin amends with their own key, and the certificate still verifies
+    let certificate = contract.get_certificate(&cert_id);
+    let mut corrected = metadata.clone();
+    corrected.title = String::from_str(&env, "Corrected Title");
+    let amendment_signature = sign_amendment(&env, &admin_key, &certificate, &corrected, 2);
+    let note = String::from_str(&env, "Corrected by the admin");
+    assert_eq!(contract.amend_certificate(&admin, &cert_id, &corrected, &metadata_root(&env, &corrected), &note, &public_key(&env, &admin_key), &amendment_signature), 2);
+    assert_eq!(contract.get_certificate_version(&cert_id, &2).recorded_by, admin);
+    assert_eq!(contract.verify_certificate(&cert_id).status, VerificationStatus::Valid);
+    
+    // Revoking the admin's key invalidates the amendment, not the issuer's key
+    let admin_public_key = BytesN::from_array(&env, &admin_key.verifying_key().to_bytes());
+    contract.revoke_signing_key(&admin, &admin_public_key);
+    assert_eq!(contract.verify_certificate(&cert_id).status, VerificationStatus::BadSignature);
+}
+
+#[test]
+fn test_amendment_cannot_replay_earlier_version() {
+    let env = create_test_env();
+    let (admin, contract) = setup_contract(&env);
+    let owner = Address::generate(&env);
+    let metadata = create_test_metadata(&env);
+    let signing_key = create_signing_key(&env, &contract, &admin, 1);
+    
+    env.mock_all_auths();
+    let signature = sign_certificate(&env, &signing_key, &admin, &owner, &metadata, None);
+    let cert_id = contract.issue_certificate(&admin, &owner, &cert_type_id(&env), &metadata, &metadata_root(&env, &metadata), &None, &vec![&env], &public_key(&env, &signing_key), &signature);
+    
+    // Signing an amendment for the wrong version number is rejected
+    let certificate = contract.get_certificate(&cert_id);
+    let mut corrected = metadata.clone();
+    corrected.title = String::from_str(&env, "Corrected Title");
+    let amendment_signature = sign_amendment(&env, &signing_key, &certificate, &corrected, 3);
+    let note = String::from_str(&env, "");
+    assert_eq!(
+        contract.try_amend_certificate(&admin, &cert_id, &corrected, &metadata_root(&env, &corrected), &note, &public_key(&env, &signing_key), &amendment_signature),
+        Err(Ok(Error::BadSignature))
+    );
+    assert_eq!(contract.get_certificate(&cert_id).metadata, metadata);
+}
+
+#[test]
+fn test_transfer_policy() {
+    let env = create_test_env();
+    let (admin, contract) = setup_contract(&env);
+    let owner = Address::generate(&env);
+    let new_owner = Address::generate(&env);
+    let metadata = create_test_metadata(&env);
+    let signing_key = create_signing_key(&env, &contract, &admin, 1);
+    
+    env.mock_all_auths();
+    let signature = sign_certificate(&env, &signing_key, &admin, &owner, &metadata, None);
+    let cert_id = contract.issue_certificate(&admin, &owner, &cert_type_id(&env), &metadata, &metadata_root(&env, &metadata), &None, &vec![&env], &public_key(&env, &signing_key), &signature);
+    
+    // Certificates are bound to their owner unless a policy says otherwise
+    assert_eq!(contract.get_transfer_policy(&cert_id), TransferPolicy::NonTransferable);
+    assert_eq!(
+        contract.try_transfer_certificate(&cert_id, &new_owner),
+        Err(Ok(Error::NotTransferable))
+    );
+    
+    // A policy for the certification type applies to its certificates, whatever
+    // their metadata says
+    contract.set_type_transfer_policy(&cert_type_id(&env), &TransferPolicy::OwnerTransferable);
+    assert_eq!(contract.get_transfer_policy(&cert_id), TransferPolicy::OwnerTransferable);
+    contract.set_type_transfer_policy(&cert_type_id(&env), &TransferPolicy::NonTransferable);
+    assert_eq!(contract.get_transfer_policy(&cert_id), TransferPolicy::NonTransferable);
+    
+    // A certificate's own policy overrides its type's policy
+    contract.set_certificate_transfer_policy(&admin, &cert_id, &TransferPolicy::IssuerApprovalRequired);
+    assert_eq!(contract.get_transfer_policy(&cert_id), TransferPolicy::IssuerApprovalRequired);
+    assert!(contract.transfer_certificate(&cert_id, &new_owner));
+    
+    // The issuer had to approve the transfer alongside both owners
+    assert_eq!(
+        env.auths().iter().map(|(address, _)| address.clone()).collect::<std::vec::Vec<_>>(),
+        std::vec![admin.clone(), owner.clone(), new_owner.clone()]
+    );
+    assert_eq!(contract.get_certificate(&cert_id).owner, new_owner);
+    
+    // A renewal keeps the certificate's own policy
+    let signature = sign_renewal(&env, &signing_key, &contract.get_certificate(&cert_id), Some(30000));
+    let renewed_id = contract.renew_certificate(&cert_id, &Some(30000), &public_key(&env, &signing_key), &signature);
+    assert_eq!(contract.get_transfer_policy(&renewed_id), TransferPolicy::IssuerApprovalRequired);
+}
+
+#[test]
+fn test_offer_and_accept_transfer() {
+    let env = create_test_env();
+    let (admin, contract) = setup_contract(&env);
+    allow_transfers(&env, &contract);
+    let owner = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    let metadata = create_test_metadata(&env);
+    let signing_key = create_signing_key(&env, &contract, &admin, 1);
+    
+    env.mock_all_auths();
+    let signature = sign_certificate(&env, &signing_key, &admin, &owner, &metadata, None);
+    let cert_id = contract.issue_certificate(&admin, &owner, &cert_type_id(&env), &metadata, &metadata_root(&env, &metadata), &None, &vec![&env], &public_key(&env, &signing_key), &signature);
+    
+    // The owner offers the certificate; only the owner authorizes the offer
+    assert_eq!(
+        contract.try_offer_transfer(&cert_id, &recipient, &12345),
+        Err(Ok(Error::InvalidExpirationDate))
+    );
+    assert!(contract.offer_transfer(&cert_id, &recipient, &13000));
+    assert_eq!(
+        env.auths().iter().map(|(address, _)| address.clone()).collect::<std::vec::Vec<_>>(),
+        std::vec![owner.clone()]
+    );
+    
+    // Both parties see the pending offer
+    assert_eq!(contract.get_pending_offers(&owner).len(), 1);
+    let offer = contract.get_pending_offers(&recipient).get(0).unwrap();
+    assert_eq!(offer.from, owner);
+    assert_eq!(offer.expires_at, 13000);
+    assert_eq!(contract.get_pending_offers(&stranger).len(), 0);
+    
+    // Only the recipient can accept; only the recipient authorizes the acceptance
+    assert_eq!(
+        contract.try_accept_transfer(&stranger, &cert_id),
+        Err(Ok(Error::NotOfferParty))
+    );
+    assert!(contract.accept_transfer(&recipient, &cert_id));
+    assert_eq!(
+        env.auths().iter().map(|(address, _)| address.clone()).collect::<std::vec::Vec<_>>(),
+        std::vec![recipient.clone()]
+    );
+    
+    // The certificate moved and the offer is gone
+    assert_eq!(contract.get_certificate(&cert_id).owner, recipient);
+    assert_eq!(contract.count_certificates_by_owner(&owner), 0);
+    assert_eq!(contract.get_pending_offers(&owner).len(), 0);
+    assert_eq!(contract.get_pending_offers(&recipient).len(), 0);
+    assert_eq!(contract.try_get_transfer_offer(&cert_id), Err(Ok(Error::OfferNotFound)));
+    assert!(contract.verify_certificate_signature(&cert_id));
+}
+
+#[test]
+fn test_cancel_and_expired_transfer_offers() {
+    let env = create_test_env();
+    let (admin, contract) = setup_contract(&env);
+    allow_transfers(&env, &contract);
+    let owner = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let metadata = create_test_metadata(&env);
+    let signing_key = create_signing_key(&env, &contract, &admin, 1);
+    
+    env.mock_all_auths();
+    let signature = sign_certificate(&env, &signing_key, &admin, &owner, &metadata, None);
+    let cert_id = contract.issue_certificate(&admin, &owner, &cert_type_id(&env), &metadata, &metadata_root(&env, &metadata), &None, &vec![&env], &public_key(&env, &signing_key), &signature);
+    
+    // The recipient can decline an offer
+    contract.offer_transfer(&cert_id, &recipient, &13000);
+    assert!(contract.cancel_transfer(&recipient, &cert_id));
+    assert_eq!(contract.get_pending_offers(&owner).len(), 0);
+    assert_eq!(contract.try_accept_transfer(&recipient, &cert_id), Err(Ok(Error::OfferNotFound)));
+    
+    // Offers cannot be accepted after they expire, and drop out of the pending list
+    contract.offer_transfer(&cert_id, &recipient, &13000);
+    env.ledger().with_mut(|ledger| ledger.timestamp = 13000);
+    assert_eq!(contract.get_pending_offers(&recipient).len(), 0);
+    assert_eq!(contract.try_accept_transfer(&recipient, &cert_id), Err(Ok(Error::OfferExpired)));
+    assert!(contract.cancel_transfer(&owner, &cert_id));
+    
+    // Non-transferable certificates cannot be offered
+    contract.set_certificate_transfer_policy(&admin, &cert_id, &TransferPolicy::NonTransferable);
+    assert_eq!(
+        contract.try_offer_transfer(&cert_id, &recipient, &14000),
+        Err(Ok(Error::NotTransferable))
+    );
+}
+
+#[test]
+fn test_issue_against_certification_types() {
+    let env = create_test_env();
+    let (admin, contract) = setup_contract(&env);
+    let owner = Address::generate(&env);
+    let metadata = create_test_metadata(&env);
+    let signing_key = create_signing_key(&env, &contract, &admin, 1);
+    let registry_id = contract.get_certification_type_registry();
+    let registry = CertificationTypeManagementContractClient::new(&env, &registry_id);
+    
+    env.mock_all_auths();
+    
+    // Without an expiration date, the type's validity period of 3 years applies
+    let signature = sign_certificate(&env, &signing_key, &admin, &owner, &metadata, None);
+    let cert_id = contract.issue_certificate(&admin, &owner, &cert_type_id(&env), &metadata, &metadata_root(&env, &metadata), &None, &vec![&env], &public_key(&env, &signing_key), &signature);
+    let certificate = contract.get_certificate(&cert_id);
+    assert_eq!(certificate.cert_type_id, cert_type_id(&env));
+    assert_eq!(certificate.expiration_date, Some(12345 + 3 * 365 * 86_400));
+    
+    // The signature covers the expiration date as submitted
+    assert!(contract.verify_certificate_signature(&cert_id));
+    assert_eq!(contract.get_certificate_version(&cert_id, &1).expiration_date, None);
+    
+    // Unknown types are rejected
+    let unknown_type = String::from_str(&env, "CERT_TYPE_X");
+    assert_eq!(
+        contract.try_issue_certificate(&admin, &owner, &unknown_type, &metadata, &metadata_root(&env, &metadata), &None, &vec![&env], &public_key(&env, &signing_key), &signature),
+        Err(Ok(Error::CertificationTypeNotFound))
+    );
+    
+    // Types with a validity period that cannot be parsed need an explicit expiration date
+    let other_type_id = registry.register_certification_type(
+        &String::from_str(&env, "ISO_14001"),
+        &String::from_str(&env, "2015"),
+        &String::from_str(&env, "Environmental Management System standard"),
+        &vec![&env, String::from_str(&env, "Manufacturing")],
+        &String::from_str(&env, "until further notice"),
+    );
+    CertifyingAuthorityClient::new(&env, &contract.get_authority_registry())
+        .add_certification_type(&admin, &other_type_id);
+    let other_owner = Address::generate(&env);
+    assert_eq!(
+        contract.try_issue_certificate(&admin, &other_owner, &other_type_id, &metadata, &metadata_root(&env, &metadata), &None, &vec![&env], &public_key(&env, &signing_key), &signature),
+        Err(Ok(Error::InvalidValidityPeriod))
+    );
+    
+    // Deprecated types are rejected
+    registry.deprecate_certification_type(&cert_type_id(&env), &String::from_str(&env, "Replaced"));
+    let signature = sign_certificate(&env, &signing_key, &admin, &other_owner, &metadata, None);
+    assert_eq!(
+        contract.try_issue_certificate(&admin, &other_owner, &cert_type_id(&env), &metadata, &metadata_root(&env, &metadata), &None, &vec![&env], &public_key(&env, &signing_key), &signature),
+        Err(Ok(Error::CertificationTypeInactive))
+    );
+}
+
+#[test]
+fn test_issue_without_type_registry() {
+    let env = create_test_env();
+    #[allow(deprecated)]
+    let contract_id = env.register_contract(None, CertificationContract);
+    let contract = CertificationContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let metadata = create_test_metadata(&env);
+    
+    env.mock_all_auths();
+    contract.initialize(&admin);
+    let signing_key = Ed25519Key::from_bytes(&[1u8; 32]);
+    let public_key = BytesN::from_array(&env, &signing_key.verifying_key().to_bytes());
+    contract.register_signing_key(&admin, &public_key, &0, &None);
+    let signature = sign_certificate(&env, &signing_key, &admin, &owner, &metadata, None);
+    
+    assert_eq!(
+        contract.try_issue_certificate(&admin, &owner, &cert_type_id(&env), &metadata, &metadata_root(&env, &metadata), &None, &vec![&env], &public_key, &signature),
+        Err(Ok(Error::TypeRegistryNotSet))
+    );
+}
+
+#[test]
+fn test_issue_requires_accreditation() {
+    let env = create_test_env();
+    let (admin, contract) = setup_contract(&env);
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let metadata = create_test_metadata(&env);
+    let signing_key = create_signing_key(&env, &contract, &admin, 1);
+    let registry = CertifyingAuthorityClient::new(&env, &contract.get_authority_registry());
+    
+    env.mock_all_auths();
+    contract.add_issuer(&issuer);
+    
+    // Issuers that are not registered authorities cannot issue
+    let signature = sign_certificate(&env, &signing_key, &issuer, &owner, &metadata, None);
+    assert_eq!(
+        contract.try_issue_certificate(&issuer, &owner, &cert_type_id(&env), &metadata, &metadata_root(&env, &metadata), &None, &vec![&env], &public_key(&env, &signing_key), &signature),
+        Err(Ok(Error::AuthorityNotFound))
+    );
+    
+    // Authorities can only issue the certification types they are accredited for
+    registry.register_authority(
+        &String::from_str(&env, "Environmental Auditors Ltd."),
+        &issuer,
+        &String::from_str(&env, "ISO 17021:2015"),
+        &vec![&env, String::from_str(&env, "CERT_TYPE_B")],
+    );
+    assert_eq!(
+        contract.try_issue_certificate(&issuer, &owner, &cert_type_id(&env), &metadata, &metadata_root(&env, &metadata), &None, &vec![&env], &public_key(&env, &signing_key), &signature),
+        Err(Ok(Error::NotAccreditedForType))
+    );
+    
+    // Accredited authorities can issue until they are deactivated
+    registry.add_certification_type(&issuer, &cert_type_id(&env));
+    let issuer_key = create_signing_key(&env, &contract, &issuer, 2);
+    let signature = sign_certificate(&env, &issuer_key, &issuer, &owner, &metadata, None);
+    let cert_id = contract.issue_certificate(&issuer, &owner, &cert_type_id(&env), &metadata, &metadata_root(&env, &metadata), &None, &vec![&env], &public_key(&env, &issuer_key), &signature);
+    
+    registry.deactivate_authority(&issuer);
+    let other_owner = Address::generate(&env);
+    let signature = sign_certificate(&env, &issuer_key, &issuer, &other_owner, &metadata, None);
+    assert_eq!(
+        contract.try_issue_certificate(&issuer, &other_owner, &cert_type_id(&env), &metadata, &metadata_root(&env, &metadata), &None, &vec![&env], &public_key(&env, &issuer_key), &signature),
+        Err(Ok(Error::AuthorityInactive))
+    );
+    
+    // Deactivated authorities cannot renew either
+    let certificate = contract.get_certificate(&cert_id);
+    let signature = sign_renewal(&env, &issuer_key, &certificate, Some(20000));
+    assert_eq!(
+        contract.try_renew_certificate(&cert_id, &Some(20000), &public_key(&env, &issuer_key), &signature),
+        Err(Ok(Error::AuthorityInactive))
+    );
+    
+    // The admin is accredited separately and can still issue
+    let signature = sign_certificate(&env, &signing_key, &admin, &other_owner, &metadata, None);
+    contract.issue_certificate(&admin, &other_owner, &cert_type_id(&env), &metadata, &metadata_root(&env, &metadata), &None, &vec![&env], &public_key(&env, &signing_key), &signature);
+}
+
+#[test]
+fn test_issue_to_registered_entity() {
+    let env = create_test_env();
+    let (admin, contract) = setup_contract(&env);
+    let entity = Address::generate(&env);
+    let metadata = create_test_metadata(&env);
+    let signing_key = create_signing_key(&env, &contract, &admin, 1);
+    
+    // Register the owner as an entity and let the contract record its certifications
+    let registry_id = env.register(CertifiableEntity, ());
+    let registry = CertifiableEntityClient::new(&env, &registry_id);
+    env.mock_all_auths();
+    registry.initialize(&admin);
+    registry.set_certification_contract(&admin, &contract.address);
+    registry.register_entity(
+        &String::from_str(&env, "Acme Manufacturing Corp"),
+        &String::from_str(&env, "Automotive"),
+        &String::from_str(&env, "San Francisco, CA"),
+        &String::from_str(&env, "contact@acmecorp.com"),
+        &entity,
+    );
+    contract.set_entity_registry(&registry_id);
+    
+    // Certificates issued to the entity are listed under it
+    let signature = sign_certificate(&env, &signing_key, &admin, &entity, &metadata, None);
+    let cert_id = contract.issue_certificate(&admin, &entity, &cert_type_id(&env), &metadata, &metadata_root(&env, &metadata), &None, &vec![&env], &public_key(&env, &signing_key), &signature);
+    let certifications = registry.list_entity_certifications(&entity);
+    assert_eq!(certifications.len(), 1);
+    assert_eq!(certifications.get(0).unwrap(), entities::certification_id(&env, &cert_id));
+    
+    // Owners that are not entities are not affected
+    let owner = Address::generate(&env);
+    let signature = sign_certificate(&env, &signing_key, &admin, &owner, &metadata, None);
+    contract.issue_certificate(&admin, &owner, &cert_type_id(&env), &metadata, &metadata_root(&env, &metadata), &None, &vec![&env], &public_key(&env, &signing_key), &signature);
+    
+    // Inactive entities cannot receive certificates
+    registry.deactivate_entity(&entity, &String::from_str(&env, "Business closure"));
+    let other_metadata = CertificateMetadata {
+        title: String::from_str(&env, "Quality Management Certificate"),
+        ..create_test_metadata(&env)
+    };
+    let signature = sign_certificate(&env, &signing_key, &admin, &entity, &other_metadata, None);
+    assert_eq!(
+        contract.try_issue_certificate(&admin, &entity, &cert_type_id(&env), &other_metadata, &metadata_root(&env, &other_metadata), &None, &vec![&env], &public_key(&env, &signing_key), &signature),
+        Err(Ok(Error::EntityInactive))
+    );
+    
+    // Issuance fails cleanly when the entity contract does not accept records from
+    // this contract
+    let other_registry_id = env.register(CertifiableEntity, ());
+    let other_registry = CertifiableEntityClient::new(&env, &other_registry_id);
+    other_registry.initialize(&admin);
+    other_registry.register_entity(
+        &String::from_str(&env, "Acme Manufacturing Corp"),
+        &String::from_str(&env, "Automotive"),
+        &String::from_str(&env, "San Francisco, CA"),
+        &String::from_str(&env, "contact@acmecorp.com"),
+        &owner,
+    );
+    contract.set_entity_registry(&other_registry_id);
+    let signature = sign_certificate(&env, &signing_key, &admin, &owner, &other_metadata, None);
+    assert_eq!(
+        contract.try_issue_certificate(&admin, &owner, &cert_type_id(&env), &other_metadata, &metadata_root(&env, &other_metadata), &None, &vec![&env], &public_key(&env, &signing_key), &signature),
+        Err(Ok(Error::EntityRegistryNotSet))
+    );
+    assert_eq!(other_registry.list_entity_certifications(&owner).len(), 0);
+}
+
+#[test]
+fn test_issue_with_evidence() {
+    let env = create_test_env();
+    let (admin, contract) = setup_contract(&env);
+    let owner = Address::generate(&env);
+    let metadata = create_test_metadata(&env);
+    let signing_key = create_signing_key(&env, &contract, &admin, 1);
+    let registry = CertificationTypeManagementContractClient::new(&env, &contract.get_certification_type_registry());
+    
+    let audit_report = String::from_str(&env, "audit_report");
+    let quality_manual = String::from_str(&env, "quality_manual");
+    registry.set_required_evidence(&cert_type_id(&env), &vec![&env, audit_report.clone(), quality_manual.clone()]);
+    
+    let audit_report_item = Evidence {
+        name: audit_report.clone(),
+        document_hash: BytesN::from_array(&env, &[7u8; 32]),
+        uri: Some(String::from_str(&env, "ipfs://audit-report")),
+    };
+    let quality_manual_item = Evidence {
+        name: quality_manual.clone(),
+        document_hash: BytesN::from_array(&env, &[8u8; 32]),
+        uri: None,
+    };
+    
+    env.mock_all_auths();
+    let signature = sign_certificate(&env, &signing_key, &admin, &owner, &metadata, None);
+    
+    // Issuance without every required item is rejected
+    let incomplete = vec![&env, audit_report_item.clone()];
+    assert_eq!(
+        contract.try_issue_certificate(&admin, &owner, &cert_type_id(&env), &metadata, &metadata_root(&env, &metadata), &None, &incomplete, &public_key(&env, &signing_key), &signature),
+        Err(Ok(Error::MissingEvidence))
+    );
+    
+    // Items cannot be submitted twice
+    let duplicated = vec![&env, audit_report_item.clone(), quality_manual_item.clone(), audit_report_item.clone()];
+    assert_eq!(
+        contract.try_issue_certificate(&admin, &owner, &cert_type_id(&env), &metadata, &metadata_root(&env, &metadata), &None, &duplicated, &public_key(&env, &signing_key), &signature),
+        Err(Ok(Error::InvalidInput))
+    );
+    
+    // A complete bundle is stored with the certificate
+    let evidence = vec![&env, audit_report_item, quality_manual_item];
+    let cert_id = contract.issue_certificate(&admin, &owner, &cert_type_id(&env), &metadata, &metadata_root(&env, &metadata), &None, &evidence, &public_key(&env, &signing_key), &signature);
+    assert_eq!(contract.get_evidence(&cert_id), evidence);
+    
+    // Auditors can check documents against what was submitted
+    assert!(contract.verify_evidence(&cert_id, &audit_report, &BytesN::from_array(&env, &[7u8; 32])));
+    assert!(!contract.verify_evidence(&cert_id, &audit_report, &BytesN::from_array(&env, &[9u8; 32])));
+    assert_eq!(
+        contract.try_verify_evidence(&cert_id, &String::from_str(&env, "floor_plan"), &BytesN::from_array(&env, &[7u8; 32])),
+        Err(Ok(Error::EvidenceNotFound))
+    );
+    
+    // Renewals keep the evidence of the original
+    let certificate = contract.get_certificate(&cert_id);
+    let signature = sign_renewal(&env, &signing_key, &certificate, Some(20000));
+    let renewed_id = contract.renew_certificate(&cert_id, &Some(20000), &public_key(&env, &signing_key), &signature);
+    assert_eq!(contract.get_evidence(&renewed_id), evidence);
+}
+
+#[test]
+fn test_batch_modes() {
+    let env = create_test_env();
+    let (admin, contract) = setup_contract(&env);
+    allow_transfers(&env, &contract);
+    let metadata = create_test_metadata(&env);
+    let signing_key = create_signing_key(&env, &contract, &admin, 1);
+    let owners = vec![&env, Address::generate(&env), Address::generate(&env), Address::generate(&env)];
+    
+    // The second item names a certification type that does not exist
+    let cert_type_ids = vec![&env, cert_type_id(&env), String::from_str(&env, "CERT_TYPE_X"), cert_type_id(&env)];
+    let mut signatures = vec![&env];
+    for owner in owners.iter() {
+        signatures.push_back(sign_certificate(&env, &signing_key, &admin, &owner, &metadata, None));
+    }
+    let metadatas = vec![&env, metadata.clone(), metadata.clone(), metadata.clone()];
+    let root = metadata_root(&env, &metadata);
+    let metadata_roots = vec![&env, root.clone(), root.clone(), root];
+    let expiration_dates = vec![&env, None, None, None];
+    let evidence_bundles = vec![&env, vec![&env], vec![&env], vec![&env]];
+    let signing_keys = vec![&env, public_key(&env, &signing_key), public_key(&env, &signing_key), public_key(&env, &signing_key)];
+    
+    env.mock_all_auths_allowing_non_root_auth();
+    
+    // All-or-nothing batches fail as a whole
+    assert_eq!(
+        contract.try_batch_issue_certificates(
+            &admin,
+            &owners,
+            &cert_type_ids,
+            &metadatas,
+            &metadata_roots,
+            &expiration_dates,
+            &evidence_bundles,
+            &signing_keys,
+            &signatures,
+            &BatchMode::AllOrNothing,
+        ),
+        Err(Ok(Error::CertificationTypeNotFound))
+    );
+    assert_eq!(contract.get_certificate_count(), 0);
+    
+    // Best-effort batches issue what they can and report the rest
+    let results = contract.batch_issue_certificates(
+        &admin,
+        &owners,
+        &cert_type_ids,
+        &metadatas,
+        &metadata_roots,
+        &expiration_dates,
+        &evidence_bundles,
+        &signing_keys,
+        &signatures,
+        &BatchMode::BestEffort,
+    );
+    assert_eq!(results.get(1).unwrap(), BatchItemResult::Failure(Error::CertificationTypeNotFound as u32));
+    let first_id = issued_id(results.get(0).unwrap());
+    let third_id = issued_id(results.get(2).unwrap());
+    assert_eq!(contract.get_certificate_count(), 2);
+    
+    // Batch revocation reports unknown certificates
+    let unknown_id = CertificateId(BytesN::from_array(&env, &[9u8; 32]));
+    let results = contract.batch_revoke_certificates(
+        &admin,
+        &vec![&env, first_id.clone(), unknown_id],
+        &RevocationReason::HolderRequest,
+        &String::from_str(&env, "Cohort cancelled"),
+        &None,
+        &BatchMode::BestEffort,
+    );
+    assert_eq!(
+        results,
+        vec![
+            &env,
+            BatchItemResult::Success(first_id.clone()),
+            BatchItemResult::Failure(Error::CertificateNotFound as u32),
+        ]
+    );
+    
+    // Batch transfers report certificates that are no longer active
+    let new_owner = Address::generate(&env);
+    let results = contract.batch_transfer_certificates(
+        &vec![&env, first_id, third_id.clone()],
+        &vec![&env, new_owner.clone(), new_owner.clone()],
+        &BatchMode::BestEffort,
+    );
+    assert_eq!(results.get(0).unwrap(), BatchItemResult::Failure(Error::CertificateWithdrawn as u32));
+    assert_eq!(results.get(1).unwrap(), BatchItemResult::Success(third_id.clone()));
+    assert_eq!(contract.get_certificate(&third_id).owner, new_owner);
+}
+
+#[test]
+fn test_batch_items_fail_individually() {
+    let env = create_test_env();
+    let (admin, contract) = setup_contract(&env);
+    let metadata = create_test_metadata(&env);
+    let signing_key = create_signing_key(&env, &contract, &admin, 1);
+    let owners = vec![&env, Address::generate(&env), Address::generate(&env), Address::generate(&env)];
+    
+    // The second item's signature covers other metadata
+    let mut signatures = vec![&env];
+    for owner in owners.iter() {
+        signatures.push_back(sign_certificate(&env, &signing_key, &admin, &owner, &metadata, None));
+    }
+    let mut other_metadata = metadata.clone();
+    other_metadata.title = String::from_str(&env, "Advanced Data Science Certificate");
+    signatures.set(1, sign_certificate(&env, &signing_key, &admin, &owners.get(1).unwrap(), &other_metadata, None));
+    
+    let cert_type_ids = vec![&env, cert_type_id(&env), cert_type_id(&env), cert_type_id(&env)];
+    let metadatas = vec![&env, metadata.clone(), metadata.clone(), metadata.clone()];
+    let root = metadata_root(&env, &metadata);
+    let metadata_roots = vec![&env, root.clone(), root.clone(), root];
+    let expiration_dates: Vec<Option<u64>> = vec![&env, None, None, None];
+    let evidence_bundles: Vec<Vec<Evidence>> = vec![&env, vec![&env], vec![&env], vec![&env]];
+    let signing_keys = vec![&env, public_key(&env, &signing_key), public_key(&env, &signing_key), public_key(&env, &signing_key)];
+    let mode = BatchMode::BestEffort;
+    
+    // The issuer authorizes the batch; the first two owners authorize their
+    // certificates and the third does not
+    let batch_args = (
+        admin.clone(),
+        owners.clone(),
+        cert_type_ids.clone(),
+        metadatas.clone(),
+        metadata_roots.clone(),
+        expiration_dates.clone(),
+        evidence_bundles.clone(),
+        signing_keys.clone(),
+        signatures.clone(),
+        mode,
+    )
+        .into_val(&env);
+    let guard = contract.get_issuance_guard();
+    let content_hashes: std::vec::Vec<BytesN<32>> = owners
+        .iter()
+        .map(|owner| {
+            verification::create_verification_message(&env, &owner, &admin, &cert_type_id(&env), &metadata, Some(&metadata_root(&env, &metadata)), None, None)
+                .try_into()
+                .unwrap()
+        })
+        .collect();
+    let owner_args: std::vec::Vec<Vec<Val>> = content_hashes.iter().map(|hash| (hash.clone(),).into_val(&env)).collect();
+    let first_owner = owners.get(0).unwrap();
+    let second_owner = owners.get(1).unwrap();
+    let owner_invokes = [
+        MockAuthInvoke { contract: &guard, fn_name: "authorize", args: owner_args[0].clone(), sub_invokes: &[] },
+        MockAuthInvoke { contract: &guard, fn_name: "authorize", args: owner_args[1].clone(), sub_invokes: &[] },
+    ];
+    env.mock_auths(&[
+        MockAuth {
+            address: &admin,
+            invoke: &MockAuthInvoke {
+                contract: &contract.address,
+                fn_name: "batch_issue_certificates",
+                args: batch_args,
+                sub_invokes: &[],
+            },
+        },
+        MockAuth { address: &first_owner, invoke: &owner_invokes[0] },
+        MockAuth { address: &second_owner, invoke: &owner_invokes[1] },
+    ]);
+    
+    // The bad signature and the missing authorization fail their own items only
+    let results = contract.batch_issue_certificates(
+        &admin,
+        &owners,
+        &cert_type_ids,
+        &metadatas,
+        &metadata_roots,
+        &expiration_dates,
+        &evidence_bundles,
+        &signing_keys,
+        &signatures,
+        &mode,
+    );
+    assert_eq!(results.get(1).unwrap(), BatchItemResult::Failure(Error::BadSignature as u32));
+    assert_eq!(results.get(2).unwrap(), BatchItemResult::Failure(Error::Unauthorized as u32));
+    let issued = contract.get_certificate(&issued_id(results.get(0).unwrap()));
+    assert_eq!(issued.owner, first_owner);
+    assert_eq!(contract.get_certificate_count(), 1);
+    assert_eq!(contract.list_certificates_by_issuer(&admin, &0, &10).certificates.len(), 1);
+}
+
+#[test]
+fn test_batch_transfer_items_fail_individually() {
+    let env = create_test_env();
+    let (admin, contract) = setup_contract(&env);
+    allow_transfers(&env, &contract);
+    let metadata = create_test_metadata(&env);
+    let signing_key = create_signing_key(&env, &contract, &admin, 1);
+    let owners = vec![&env, Address::generate(&env), Address::generate(&env), Address::generate(&env)];
+    let new_owners = vec![&env, Address::generate(&env), Address::generate(&env), Address::generate(&env)];
+    
+    env.mock_all_auths_allowing_non_root_auth();
+    let mut certificate_ids = vec![&env];
+    for owner in owners.iter() {
+        let signature = sign_certificate(&env, &signing_key, &admin, &owner, &metadata, None);
+        certificate_ids.push_back(contract.issue_certificate(&admin, &owner, &cert_type_id(&env), &metadata, &metadata_root(&env, &metadata), &None, &vec![&env], &public_key(&env, &signing_key), &signature));
+    }
+    
+    // Every owner authorizes their transfer, but the second new owner does not
+    // accept theirs
+    let guard = contract.get_issuance_guard();
+    let transfer_args: std::vec::Vec<Vec<Val>> = (0..3)
+        .map(|i| {
+            let hash = transfer::transfer_hash(&env, &certificate_ids.get(i).unwrap(), &new_owners.get(i).unwrap());
+            (hash,).into_val(&env)
+        })
+        .collect();
+    let invokes: std::vec::Vec<MockAuthInvoke> = transfer_args
+        .iter()
+        .map(|args| MockAuthInvoke { contract: &guard, fn_name: "authorize", args: args.clone(), sub_invokes: &[] })
+        .collect();
+    let owner_addresses: std::vec::Vec<Address> = owners.iter().collect();
+    let new_owner_addresses: std::vec::Vec<Address> = new_owners.iter().collect();
+    env.mock_auths(&[
+        MockAuth { address: &owner_addresses[0], invoke: &invokes[0] },
+        MockAuth { address: &new_owner_addresses[0], invoke: &invokes[0] },
+        MockAuth { address: &owner_addresses[1], invoke: &invokes[1] },
+        MockAuth { address: &owner_addresses[2], invoke: &invokes[2] },
+        MockAuth { address: &new_owner_addresses[2], invoke: &invokes[2] },
+    ]);
+    
+    // The transfer without every authorization fails on its own
+    let results = contract.batch_transfer_certificates(&certificate_ids, &new_owners, &BatchMode::BestEffort);
+    assert_eq!(results.get(0).unwrap(), BatchItemResult::Success(certificate_ids.get(0).unwrap()));
+    assert_eq!(results.get(1).unwrap(), BatchItemResult::Failure(Error::Unauthorized as u32));
+    assert_eq!(results.get(2).unwrap(), BatchItemResult::Success(certificate_ids.get(2).unwrap()));
+    assert_eq!(contract.get_certificate(&certificate_ids.get(0).unwrap()).owner, new_owners.get(0).unwrap());
+    assert_eq!(contract.get_certificate(&certificate_ids.get(1).unwrap()).owner, owners.get(1).unwrap());
+    assert_eq!(contract.get_certificate(&certificate_ids.get(2).unwrap()).owner, new_owners.get(2).unwrap());
+    
+    // Certificates that need the issuer's approval fail without it
+    env.mock_all_auths();
+    contract.set_type_transfer_policy(&cert_type_id(&env), &TransferPolicy::IssuerApprovalRequired);
+    let invoke = &invokes[1];
+    env.mock_auths(&[
+        MockAuth { address: &owner_addresses[1], invoke },
+        MockAuth { address: &new_owner_addresses[1], invoke },
+    ]);
+    let results = contract.batch_transfer_certificates(
+        &vec![&env, certificate_ids.get(1).unwrap()],
+        &vec![&env, new_owners.get(1).unwrap()],
+        &BatchMode::BestEffort,
+    );
+    assert_eq!(results.get(0).unwrap(), BatchItemResult::Failure(Error::Unauthorized as u32));
+    assert_eq!(contract.get_certificate(&certificate_ids.get(1).unwrap()).owner, owners.get(1).unwrap());
+}
+
+#[test]
+fn test_batch_size_limit() {
+    let env = create_test_env();
+    let (admin, contract) = setup_contract(&env);
+    
+    env.mock_all_auths();
+    
+    // Batches larger than the maximum are rejected before any item runs
+    let mut certificate_ids = vec![&env];
+    for i in 0..=batch::MAX_BATCH_SIZE {
+        certificate_ids.push_back(CertificateId(BytesN::from_array(&env, &[i as u8; 32])));
+    }
+    assert_eq!(
+        contract.try_batch_revoke_certificates(
+            &admin,
+            &certificate_ids,
+            &RevocationReason::HolderRequest,
+            &String::from_str(&env, "Cohort cancelled"),
+            &None,
+            &BatchMode::BestEffort,
+        ),
+        Err(Ok(Error::BatchTooLarge))
+    );
+}
+
+#[test]
+fn test_selective_disclosure() {
+    let env = create_test_env();
+    let (admin, contract) = setup_contract(&env);
+    let owner = Address::generate(&env);
+    let signing_key = create_signing_key(&env, &contract, &admin, 1);
+    
+    // The issuer commits to every field, including the confidential description
+    // and audit score, but only puts the public ones on-chain
+    let mut committed = create_test_metadata(&env);
+    committed.description = String::from_str(&env, "Two minor nonconformities in supplier control");
+    committed.achievement_type = String::from_str(&env, "ISO 9001");
+    committed.additional_data.set(
+        String::from_str(&env, "audit_score"),
+        BytesN::from_array(&env, &[2u8; 32]),
+    );
+    let (salts, leaves) = commit_fields(&env, &metadata_fields(&env, &committed));
+    let root = merkle_root(&env, &leaves);
+    
+    let mut metadata = committed.clone();
+    metadata.description = String::from_str(&env, "");
+    metadata.additional_data.remove(String::from_str(&env, "audit_score"));
+    
+    // The issuer's signature covers the root, so another root is refused
+    env.mock_all_auths();
+    let signature = sign_committed_certificate(&env, &signing_key, &admin, &owner, &metadata, &root, None);
+    assert_eq!(
+        contract.try_issue_certificate(&admin, &owner, &cert_type_id(&env), &metadata, &metadata_root(&env, &metadata), &None, &vec![&env], &public_key(&env, &signing_key), &signature),
+        Err(Ok(Error::BadSignature))
+    );
+    let cert_id = contract.issue_certificate(&admin, &owner, &cert_type_id(&env), &metadata, &root, &None, &vec![&env], &public_key(&env, &signing_key), &signature);
+    assert_eq!(contract.get_metadata_root(&cert_id), root);
+    
+    // The confidential values are not on the ledger
+    let certificate = contract.get_certificate(&cert_id);
+    assert_eq!(certificate.metadata.description, String::from_str(&env, ""));
+    assert!(!certificate.metadata.additional_data.contains_key(String::from_str(&env, "audit_score")));
+    
+    // The holder discloses the achievement type and one additional data entry
+    // with their salts and proofs, but not the description or audit score
+    let achievement_type = DisclosedField::AchievementType(String::from_str(&env, "ISO 9001"));
+    let course_id = DisclosedField::AdditionalData(
+        String::from_str(&env, "course_id"),
+        BytesN::from_array(&env, &[1u8; 32]),
+    );
+    let fields = vec![&env, achievement_type.clone(), course_id];
+    let field_salts = vec![&env, salts.get(2).unwrap(), salts.get(4).unwrap()];
+    let proofs = vec![&env, merkle_proof(&env, &leaves, 2), merkle_proof(&env, &leaves, 4)];
+    assert!(contract.verify_disclosed_fields(&cert_id, &fields, &field_salts, &proofs));
+    
+    // The confidential description can still be proven to a chosen verifier
+    assert!(contract.verify_disclosed_fields(
+        &cert_id,
+        &vec![&env, DisclosedField::Description(committed.description.clone())],
+        &vec![&env, salts.get(1).unwrap()],
+        &vec![&env, merkle_proof(&env, &leaves, 1)],
+    ));
+    
+    // A field with a different value, or the right value with another salt, does not verify
+    let forged = DisclosedField::AchievementType(String::from_str(&env, "ISO 14001"));
+    assert!(!contract.verify_disclosed_fields(&cert_id, &vec![&env, forged], &vec![&env, salts.get(2).unwrap()], &vec![&env, proofs.get(0).unwrap()]));
+    assert!(!contract.verify_disclosed_fields(&cert_id, &vec![&env, achievement_type.clone()], &vec![&env, salts.get(3).unwrap()], &vec![&env, proofs.get(0).unwrap()]));
+    assert_eq!(
+        contract.try_verify_disclosed_fields(&cert_id, &fields, &field_salts, &vec![&env, proofs.get(0).unwrap()]),
+        Err(Ok(Error::InputLengthMismatch))
+    );
+    assert_eq!(
+        contract.try_verify_disclosed_fields(&cert_id, &fields, &vec![&env, salts.get(2).unwrap()], &proofs),
+        Err(Ok(Error::InputLengthMismatch))
+    );
+    
+    // Amendments commit to a new root
+    let mut amended = metadata.clone();
+    amended.achievement_type = String::from_str(&env, "ISO 9001:2015");
+    let signature = sign_amendment(&env, &signing_key, &certificate, &amended, 2);
+    contract.amend_certificate(&admin, &cert_id, &amended, &metadata_root(&env, &amended), &String::from_str(&env, "Scope updated"), &public_key(&env, &signing_key), &signature);
+    assert_eq!(contract.get_metadata_root(&cert_id), metadata_root(&env, &amended));
+    assert!(!contract.verify_disclosed_fields(&cert_id, &vec![&env, achievement_type], &vec![&env, salts.get(2).unwrap()], &vec![&env, proofs.get(0).unwrap()]));
+    assert!(contract.verify_certificate_signature(&cert_id));
+}
+
+#[test]
+fn test_expiring_certificates() {
+    let env = create_test_env();
+    let (admin, contract) = setup_contract(&env);
+    allow_transfers(&env, &contract);
+    let owner = Address::generate(&env);
+    let other_owner = Address::generate(&env);
+    let signing_key = create_signing_key(&env, &contract, &admin, 1);
+    let day = 24 * 60 * 60;
+    
+    env.mock_all_auths();
+    let issue = |owner: &Address, title: &str, expiration_date: Option<u64>| {
+        let mut metadata = create_test_metadata(&env);
+        metadata.title = String::from_str(&env, title);
+        let signature = sign_certificate(&env, &signing_key, &admin, owner, &metadata, expiration_date);
+        contract.issue_certificate(&admin, owner, &cert_type_id(&env), &metadata, &metadata_root(&env, &metadata), &expiration_date, &vec![&env], &public_key(&env, &signing_key), &signature)
+    };
+    let soon_id = issue(&owner, "Soon", Some(12345 + 10 * day));
+    let later_id = issue(&owner, "Later", Some(12345 + 40 * day));
+    let other_id = issue(&other_owner, "Other", Some(12345 + 60 * day));
+    issue(&owner, "Outside", Some(12345 + 120 * day));
+    
+    // The 90-day pipeline lists certificates in expiration week order
+    let from = 12345;
+    let to = 12345 + 90 * day;
+    let ids = |page: &ExpiringCertificatePage| -> Vec<CertificateId> {
+        let mut ids = Vec::new(&env);
+        for certificate in page.certificates.iter() {
+            ids.push_back(certificate.id);
+        }
+        ids
+    };
+    let page = contract.list_expiring_certificates(&from, &to, &0, &10);
+    assert_eq!(ids(&page), vec![&env, soon_id.clone(), later_id.clone(), other_id.clone()]);
+    assert_eq!(page.next_cursor, None);
+    
+    // Pages follow the cursor
+    let page = contract.list_expiring_certificates(&from, &to, &0, &2);
+    assert_eq!(ids(&page), vec![&env, soon_id.clone(), later_id.clone()]);
+    assert_eq!(page.next_cursor, Some(2));
+    let page = contract.list_expiring_certificates(&from, &to, &2, &2);
+    assert_eq!(ids(&page), vec![&env, other_id.clone()]);
+    assert_eq!(page.next_cursor, None);
+    
+    // Owners see only their own certificates
+    let page = contract.list_owner_expiring_certificates(&owner, &from, &to, &0, &10);
+    assert_eq!(ids(&page), vec![&env, soon_id.clone(), later_id.clone()]);
+    
+    // Revoked certificates drop out, and transferred ones follow their new owner
+    let note = String::from_str(&env, "Withdrawn");
+    contract.revoke_certificate(&admin, &later_id, &RevocationReason::HolderRequest, &note, &None);
+    assert!(contract.transfer_certificate(&other_id, &owner));
+    let page = contract.list_owner_expiring_certificates(&owner, &from, &to, &0, &10);
+    assert_eq!(ids(&page), vec![&env, soon_id.clone(), other_id.clone()]);
+    let page = contract.list_owner_expiring_certificates(&other_owner, &from, &to, &0, &10);
+    assert_eq!(page.certificates.len(), 0);
+    
+    // Windows are at most 26 weeks and must not be reversed
+    assert_eq!(contract.try_list_expiring_certificates(&to, &from, &0, &10), Err(Ok(Error::InvalidExpiryWindow)));
+    assert_eq!(contract.try_list_expiring_certificates(&from, &(from + 365 * day), &0, &10), Err(Ok(Error::InvalidExpiryWindow)));
+
+}
+
+#[test]
+fn test_process_expirations() {
+    let env = create_test_env();
+    let (admin, contract) = setup_contract(&env);
+    let owner = Address::generate(&env);
+    let keeper = Address::generate(&env);
+    let signing_key = create_signing_key(&env, &contract, &admin, 1);
+    let day = 24 * 60 * 60;
+    
+    env.mock_all_auths();
+    let issue = |title: &str, expiration_date: Option<u64>| {
+        let mut metadata = create_test_metadata(&env);
+        metadata.title = String::from_str(&env, title);
+        let signature = sign_certificate(&env, &signing_key, &admin, &owner, &metadata, expiration_date);
+        contract.issue_certificate(&admin, &owner, &cert_type_id(&env), &metadata, &metadata_root(&env, &metadata), &expiration_date, &vec![&env], &public_key(&env, &signing_key), &signature)
+    };
+    let stored_status = |cert_id: &CertificateId| {
+        env.as_contract(&contract.address, || storage::get_certificate(&env, cert_id).unwrap().status)
+    };
+    let expired_events = || {
+        let topics: Vec<Val> = (Symbol::new(&env, "certificate_expired"),).into_val(&env);
+        env.events().all().iter().filter(|event| event.1 == topics).count()
+    };
+    
+    let first_id = issue("First", Some(12345 + day));
+    let revoked_id = issue("Revoked", Some(12345 + 2 * day));
+    let later_id = issue("Later", Some(12345 + 10 * day));
+    issue("Permanent", None);
+    let note = String::from_str(&env, "Withdrawn");
+    contract.revoke_certificate(&admin, &revoked_id, &RevocationReason::HolderRequest, &note, &None);
+    
+    // Nothing has expired yet
+    assert_eq!(contract.process_expirations(&keeper, &10), 0);
+    
+    // Expired certificates are marked and announced; revoked ones are left alone
+    env.ledger().with_mut(|ledger| ledger.timestamp = 12345 + 3 * day);
+    assert_eq!(stored_status(&first_id), CertificateStatus::Active);
+    assert_eq!(contract.process_expirations(&keeper, &10), 1);
+    assert_eq!(expired_events(), 1);
+    assert_eq!(stored_status(&first_id), CertificateStatus::Expired);
+    assert_eq!(stored_status(&revoked_id), CertificateStatus::Withdrawn);
+    assert_eq!(stored_status(&later_id), CertificateStatus::Active);
+    
+    // The sweep resumes where it stopped
+    assert_eq!(contract.process_expirations(&keeper, &10), 0);
+    
+    // Callers are paid from the contract's balance of the reward token, as far as it goes
+    let token = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    StellarAssetClient::new(&env, &token).mint(&contract.address, &15);
+    assert_eq!(
+        contract.try_set_expiry_reward(&Some(ExpiryReward { token: token.clone(), amount: -1 })),
+        Err(Ok(Error::InvalidInput))
+    );
+    let reward = ExpiryReward { token: token.clone(), amount: 10 };
+    contract.set_expiry_reward(&Some(reward.clone()));
+    assert_eq!(contract.get_expiry_reward(), Some(reward));
+    
+    let token_client = TokenClient::new(&env, &token);
+    let second_id = issue("Second", Some(12345 + 11 * day));
+    env.ledger().with_mut(|ledger| ledger.timestamp = 12345 + 12 * day);
+    
+    // `limit` bounds the entries visited in one call
+    assert_eq!(contract.process_expirations(&keeper, &1), 1);
+    assert_eq!(stored_status(&later_id), CertificateStatus::Expired);
+    assert_eq!(stored_status(&second_id), CertificateStatus::Active);
+    assert_eq!(token_client.balance(&keeper), 10);
+    assert_eq!(contract.process_expirations(&keeper, &1), 1);
+    assert_eq!(stored_status(&second_id), CertificateStatus::Expired);
+    assert_eq!(token_client.balance(&keeper), 15);
+    assert_eq!(token_client.balance(&contract.address), 0);
+    
+    // Certificates indexed behind the sweep are still processed
+    let backdated_id = issue("Backdated", Some(12345 + 5 * day));
+    assert_eq!(contract.process_expirations(&keeper, &10), 1);
+    assert_eq!(stored_status(&backdated_id), CertificateStatus::Expired);
+    
+    contract.set_expiry_reward(&None);
+    assert_eq!(contract.get_expiry_reward(), None);
 }
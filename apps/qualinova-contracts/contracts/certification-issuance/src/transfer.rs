@@ -1,104 +1,195 @@
-use crate::{Certificate, CertificateId};
-use alloc::format;
-use soroban_sdk::{Address, Bytes, Env, Symbol, Vec};
+use crate::{
+    access_control, amendment, expiry, guard, index, lifecycle, offer, storage, Certificate,
+    CertificateId, CertificateStatus, Error, TransferPolicy,
+};
 use soroban_sdk::xdr::ToXdr;
-
-// Storage keys format
-const OWNER_CERTS_PREFIX: &str = "owner_certs";
+use soroban_sdk::{vec, Address, Bytes, BytesN, Env, String, Symbol};
 
 // Events
 const CERTIFICATE_TRANSFERRED_EVENT: &str = "certificate_transferred";
+const TRANSFER_POLICY_SET_EVENT: &str = "transfer_policy_set";
 
 // Transfer a certificate to a new owner
-pub fn transfer_certificate(env: &Env, certificate_id: CertificateId, new_owner: Address) -> bool {
+pub fn transfer_certificate(
+    env: &Env,
+    certificate_id: CertificateId,
+    new_owner: Address,
+) -> Result<bool, Error> {
     // Get the certificate
-    let cert_id_bytes = env.crypto().sha256(&Bytes::from_slice(env, &certificate_id.0.to_array()));
-    let certificate_key = get_certificate_key(env, &cert_id_bytes);
-    
-    let mut certificate: Certificate = env
-        .storage()
-        .persistent()
-        .get(&certificate_key)
-        .unwrap_or_else(|| panic!("Certificate not found"));
-    
-    // Check if the certificate is revoked
-    if certificate.revoked {
-        return false;
+    let certificate = storage::get_certificate(env, &certificate_id)
+        .ok_or(Error::CertificateNotFound)?;
+    
+    // Only active certificates can change hands
+    if lifecycle::effective_status(env, &certificate) != CertificateStatus::Active {
+        return Ok(false);
     }
     
+    // Check the transfer policy
+    require_transfer_allowed(env, &certificate)?;
+    
     // Require authorization from both current and new owner
     certificate.owner.require_auth();
     new_owner.require_auth();
     
-    // Update owner lists
-    remove_from_owner_certificates(env, &certificate.owner, &certificate_id);
-    add_to_owner_certificates(env, &new_owner, &certificate_id);
+    move_certificate(env, certificate, new_owner);
+    
+    Ok(true)
+}
+
+// Transfer a certificate as an item of a batch. The owners and, if the policy
+// requires it, the issuer authorize the transfer through the issuance guard, so
+// a missing authorization fails this item with `Unauthorized` instead of
+// aborting the whole batch.
+pub fn transfer_batch_item(
+    env: &Env,
+    certificate_id: CertificateId,
+    new_owner: Address,
+) -> Result<bool, Error> {
+    // Get the certificate
+    let certificate = storage::get_certificate(env, &certificate_id)
+        .ok_or(Error::CertificateNotFound)?;
+    
+    // Only active certificates can change hands
+    if lifecycle::effective_status(env, &certificate) != CertificateStatus::Active {
+        return Ok(false);
+    }
+    
+    // Check the transfer policy and collect everyone who must authorize
+    let mut signers = vec![env, certificate.owner.clone(), new_owner.clone()];
+    if requires_issuer_approval(env, &certificate)? {
+        signers.push_back(certificate.issuer.clone());
+    }
+    
+    let content_hash = transfer_hash(env, &certificate_id, &new_owner);
+    for signer in signers.iter() {
+        if !guard::is_authorized(env, &signer, &content_hash)? {
+            return Err(Error::Unauthorized);
+        }
+    }
+    
+    move_certificate(env, certificate, new_owner);
+    
+    Ok(true)
+}
+
+// Hash of a certificate and its new owner, which the parties of a batched
+// transfer authorize
+pub fn transfer_hash(env: &Env, certificate_id: &CertificateId, new_owner: &Address) -> BytesN<32> {
+    let mut data = Bytes::new(env);
+    data.append(&certificate_id.clone().to_xdr(env));
+    data.append(&new_owner.clone().to_xdr(env));
+    
+    env.crypto().sha256(&data).into()
+}
+
+// Move a certificate to its new owner once every check has passed
+pub fn move_certificate(env: &Env, mut certificate: Certificate, new_owner: Address) {
+    let certificate_id = certificate.id.clone();
+    
+    // A pending offer for the certificate no longer applies
+    offer::clear_offer(env, &certificate_id);
+    
+    // Update owner indexes
+    index::move_owner(env, &certificate, &new_owner);
+    expiry::move_owner_expiration(env, &certificate, &new_owner);
     
     // Store the old owner for the event
     let old_owner = certificate.owner.clone();
     
-    // Update the certificate
+    // The issuer signed for the original owner, so keep the issued content
+    // as version 1 before the owner changes
+    amendment::record_initial_version(env, &certificate);
+    
+    // Update the certificate, settling any suspension that has lifted
     certificate.owner = new_owner.clone();
-    env.storage().persistent().set(&certificate_key, &certificate);
+    certificate.status = CertificateStatus::Active;
+    storage::set_certificate(env, &certificate);
     
     // Emit certificate transferred event
     env.events().publish(
         (Symbol::new(env, CERTIFICATE_TRANSFERRED_EVENT),),
         (certificate_id, old_owner, new_owner),
     );
+}
+
+// Set the transfer policy of a single certificate, overriding its type's policy
+pub fn set_certificate_transfer_policy(
+    env: &Env,
+    issuer: Address,
+    certificate_id: CertificateId,
+    policy: TransferPolicy,
+) -> Result<bool, Error> {
+    // Get the certificate
+    let certificate = storage::get_certificate(env, &certificate_id)
+        .ok_or(Error::CertificateNotFound)?;
+    
+    // Only the issuing issuer or the admin may set the policy
+    access_control::require_certificate_issuer(env, &issuer, &certificate)?;
     
-    true
+    storage::set_certificate_transfer_policy(env, &certificate_id, policy);
+    
+    // Emit transfer policy set event
+    env.events().publish(
+        (Symbol::new(env, TRANSFER_POLICY_SET_EVENT),),
+        (issuer, certificate_id, policy),
+    );
+    
+    Ok(true)
 }
 
-// Remove a certificate from the owner's list
-fn remove_from_owner_certificates(env: &Env, owner: &Address, cert_id: &CertificateId) {
-    let owner_key = get_owner_key(env, owner);
-    
-    let owner_certs: Vec<CertificateId> = env
-        .storage()
-        .persistent()
-        .get(&owner_key)
-        .unwrap_or_else(|| Vec::new(env));
-    
-    // Create a new list without the removed certificate
-    let mut new_owner_certs = Vec::new(env);
-    
-    for i in 0..owner_certs.len() {
-        let current = owner_certs.get(i).unwrap();
-        if current.0 != cert_id.0 {
-            new_owner_certs.push_back(current);
-        }
-    }
+// Set the transfer policy of every certificate of a certification type, unless a
+// certificate has its own policy. The type is shared by all issuers accredited
+// for it, so only the admin may set its policy.
+pub fn set_type_transfer_policy(
+    env: &Env,
+    cert_type_id: String,
+    policy: TransferPolicy,
+) -> Result<bool, Error> {
+    // Verify the caller is the admin
+    let admin = access_control::require_admin(env)?;
+    
+    storage::set_type_transfer_policy(env, &cert_type_id, policy);
+    
+    // Emit transfer policy set event
+    env.events().publish(
+        (Symbol::new(env, TRANSFER_POLICY_SET_EVENT),),
+        (admin, cert_type_id, policy),
+    );
     
-    // Update the owner's certificates list
-    env.storage().persistent().set(&owner_key, &new_owner_certs);
+    Ok(true)
 }
 
-// Add a certificate to the owner's list
-fn add_to_owner_certificates(env: &Env, owner: &Address, cert_id: &CertificateId) {
-    let owner_key = get_owner_key(env, owner);
-    
-    let mut owner_certs: Vec<CertificateId> = env
-        .storage()
-        .persistent()
-        .get(&owner_key)
-        .unwrap_or_else(|| Vec::new(env));
-    
-    owner_certs.push_back(cert_id.clone());
-    env.storage().persistent().set(&owner_key, &owner_certs);
+// Get the transfer policy that applies to a certificate
+pub fn get_transfer_policy(env: &Env, certificate_id: CertificateId) -> Result<TransferPolicy, Error> {
+    let certificate = storage::get_certificate(env, &certificate_id)
+        .ok_or(Error::CertificateNotFound)?;
+    
+    Ok(effective_transfer_policy(env, &certificate))
 }
 
-// Helper function to get a certificate key from a hash
-fn get_certificate_key(env: &Env, hash: &soroban_sdk::crypto::Hash<32>) -> Symbol {
-    // Create a simple key with the hash - avoid using colons
-    let key = format!("cert{:x}", hash.to_array()[0]);
-    Symbol::new(env, &key)
+// Resolve a certificate's transfer policy: its own policy, then the policy of
+// its certification type, then non-transferable
+pub fn effective_transfer_policy(env: &Env, certificate: &Certificate) -> TransferPolicy {
+    storage::get_certificate_transfer_policy(env, &certificate.id)
+        .or_else(|| storage::get_type_transfer_policy(env, &certificate.cert_type_id))
+        .unwrap_or(TransferPolicy::NonTransferable)
 }
 
-// Helper function to get an owner key
-fn get_owner_key(env: &Env, owner: &Address) -> Symbol {
-    let owner_hash = env.crypto().sha256(&owner.clone().to_xdr(env));
-    // Avoid using colons in Symbol keys
-    let key = format!("{}_{:x}", OWNER_CERTS_PREFIX, owner_hash.to_array()[0]);
-    Symbol::new(env, &key)
+// Check the certificate's transfer policy, requiring the issuer's approval if needed
+pub fn require_transfer_allowed(env: &Env, certificate: &Certificate) -> Result<(), Error> {
+    if requires_issuer_approval(env, certificate)? {
+        certificate.issuer.require_auth();
+    }
+    
+    Ok(())
+}
+
+// Check that the certificate's transfer policy allows transfers, and whether
+// the issuer must approve them
+fn requires_issuer_approval(env: &Env, certificate: &Certificate) -> Result<bool, Error> {
+    match effective_transfer_policy(env, certificate) {
+        TransferPolicy::NonTransferable => Err(Error::NotTransferable),
+        TransferPolicy::OwnerTransferable => Ok(false),
+        TransferPolicy::IssuerApprovalRequired => Ok(true),
+    }
 }
@@ -0,0 +1,88 @@
+use crate::{access_control, migration, Error};
+use soroban_sdk::{BytesN, Env, Symbol};
+
+// Version of the storage layout this build reads and writes. Bump it with every
+// change to stored data that existing deployments must be migrated for, and add
+// the step that migrates to it to `migrate`.
+pub const SCHEMA_VERSION: u32 = 1;
+
+// Schema version of deployments that predate schema versioning. They store
+// certificates under the legacy one-byte keys.
+const UNVERSIONED_SCHEMA_VERSION: u32 = 0;
+
+// Instance storage key of the stored schema version
+const SCHEMA_VERSION_KEY: &str = "schema_version";
+
+// Events
+const CONTRACT_UPGRADED_EVENT: &str = "contract_upgraded";
+const SCHEMA_MIGRATED_EVENT: &str = "schema_migrated";
+
+// Replace the contract's code with an uploaded Wasm. Stored data is kept as it
+// is; call `migrate` with the new code to bring it to the new schema version.
+pub fn upgrade(env: &Env, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+    // Verify the caller is the admin
+    let admin = access_control::require_admin(env)?;
+    
+    env.deployer()
+        .update_current_contract_wasm(new_wasm_hash.clone());
+    
+    // Emit contract upgraded event
+    env.events().publish(
+        (Symbol::new(env, CONTRACT_UPGRADED_EVENT),),
+        (admin, new_wasm_hash),
+    );
+    
+    Ok(())
+}
+
+// Migrate stored data from its schema version to the one of this build, one
+// version at a time. Returns the schema version reached.
+pub fn migrate(env: &Env) -> Result<u32, Error> {
+    // Verify the caller is the admin
+    access_control::require_admin(env)?;
+    
+    let from = get_schema_version(env);
+    if from > SCHEMA_VERSION {
+        return Err(Error::SchemaVersionTooNew);
+    }
+    
+    // Steps that migrate stored data from each older version to the next go
+    // here, in order. A step runs within a single call, so it may only rewrite a
+    // bounded number of entries; data that grows with the number of certificates
+    // is moved by a batched, admin-only entrypoint such as `migrate_legacy_storage`.
+    
+    // 0 -> 1: certificates move from the legacy one-byte keys to the typed keys
+    // in batches of `migrate_legacy_storage`. Until those have covered every
+    // slot, the data stays at version 0.
+    if from < 1 && !migration::is_legacy_storage_migrated(env) {
+        return Ok(from);
+    }
+    
+    if from < SCHEMA_VERSION {
+        set_schema_version(env);
+        
+        // Emit schema migrated event
+        env.events().publish(
+            (Symbol::new(env, SCHEMA_MIGRATED_EVENT),),
+            (from, SCHEMA_VERSION),
+        );
+    }
+    
+    Ok(SCHEMA_VERSION)
+}
+
+// Get the schema version of the stored data
+pub fn get_schema_version(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&Symbol::new(env, SCHEMA_VERSION_KEY))
+        .unwrap_or(UNVERSIONED_SCHEMA_VERSION)
+}
+
+// Record that the stored data has the schema version of this build
+pub fn set_schema_version(env: &Env) {
+    env.storage()
+        .instance()
+        .set(&Symbol::new(env, SCHEMA_VERSION_KEY), &SCHEMA_VERSION);
+}
+
@@ -1,87 +1,162 @@
-use crate::{Certificate, CertificateId};
-use alloc::format;
-use soroban_sdk::{Address, Bytes, BytesN, Env, Symbol};
+use crate::{
+    access_control, amendment, guard, lifecycle, signing_keys, storage, Certificate, CertificateId,
+    CertificateMetadata, CertificateStatus, CertificateVersion, Error, VerificationReport,
+    VerificationStatus,
+};
+use soroban_sdk::{Address, Bytes, BytesN, Env, String};
 use soroban_sdk::xdr::ToXdr;
 
 // Verify a certificate's signature
 pub fn verify_certificate_signature(env: &Env, certificate_id: CertificateId) -> bool {
+    verify_certificate(env, certificate_id).status == VerificationStatus::Valid
+}
+
+// Verify a certificate and report the reason it is or is not valid
+pub fn verify_certificate(env: &Env, certificate_id: CertificateId) -> VerificationReport {
+    let checked_at = env.ledger().timestamp();
+    
     // Get the certificate
-    let cert_id_bytes = env.crypto().sha256(&Bytes::from_slice(env, &certificate_id.0.to_array()));
-    let certificate_key = get_certificate_key(env, &cert_id_bytes);
-    
-    let certificate: Certificate = env
-        .storage()
-        .persistent()
-        .get(&certificate_key)
-        .unwrap_or_else(|| panic!("Certificate not found"));
-    
-    // Check if the certificate is revoked
-    if certificate.revoked {
-        return false;
+    let certificate = match storage::get_certificate(env, &certificate_id) {
+        Some(certificate) => certificate,
+        None => {
+            return VerificationReport {
+                certificate_id,
+                status: VerificationStatus::NotFound,
+                checked_at,
+                issuer: None,
+                issuer_active: false,
+                signing_key: BytesN::from_array(env, &[0u8; 32]),
+                issuance_date: None,
+                expiration_date: None,
+            }
+        }
+    };
+    
+    let issuer_active = access_control::is_issuer(env, certificate.issuer.clone());
+    let status = get_verification_status(env, &certificate, issuer_active);
+    
+    VerificationReport {
+        certificate_id,
+        status,
+        checked_at,
+        issuer: Some(certificate.issuer),
+        issuer_active,
+        signing_key: certificate.signing_key,
+        issuance_date: Some(certificate.issuance_date),
+        expiration_date: certificate.expiration_date,
+    }
+}
+
+// Work out the status of a stored certificate, most severe reason first
+fn get_verification_status(
+    env: &Env,
+    certificate: &Certificate,
+    issuer_active: bool,
+) -> VerificationStatus {
+    // Check the lifecycle status: withdrawn, then expired, then suspended or superseded
+    match lifecycle::effective_status(env, certificate) {
+        CertificateStatus::Active => {}
+        CertificateStatus::Withdrawn => return VerificationStatus::Revoked,
+        CertificateStatus::Expired => return VerificationStatus::Expired,
+        CertificateStatus::Suspended => return VerificationStatus::Suspended,
+        CertificateStatus::Superseded => return VerificationStatus::Superseded,
     }
     
-    // Check if the certificate is expired
-    if let Some(expiration) = certificate.expiration_date {
-        let current_time = env.ledger().timestamp();
-        if current_time > expiration {
-            return false;
-        }
+    // Check if the issuer may still issue
+    if !issuer_active {
+        return VerificationStatus::IssuerInactive;
     }
     
-    // Verify the signature
-    let message = create_verification_message(env, &certificate);
-    let issuer_public_key = get_issuer_public_key(env, &certificate.issuer);
+    // The signing key must be registered to whoever signed the current version,
+    // the issuer or the admin for an amendment, not revoked, and valid when the
+    // version was signed
+    let version = amendment::current_version(env, certificate);
+    let signing_key = match signing_keys::find_signing_key(env, &version.recorded_by, &version.signing_key) {
+        Some(key) => key,
+        None => return VerificationStatus::BadSignature,
+    };
+    if !signing_keys::is_valid_at(&signing_key, version.recorded_at) {
+        return VerificationStatus::BadSignature;
+    }
     
-    // Convert the signature to BytesN<64> for ed25519_verify
-    // This is a simplification - in a real implementation, you'd ensure the signature is valid
-    let signature_bytes = BytesN::<64>::from_array(env, &[0; 64]);
+    // Check the signature. Without an issuance guard it cannot be checked.
+    if check_version_signature(env, &version).is_err() {
+        return VerificationStatus::BadSignature;
+    }
     
-    // Verify the signature using ed25519
-    let _result = env.crypto().ed25519_verify(
-        &issuer_public_key,
-        &message,
-        &signature_bytes
-    );
+    VerificationStatus::Valid
+}
+
+// Check the signature over a version of a certificate with its signing key.
+// The check runs through the issuance guard, so a mismatch is returned as
+// `BadSignature` instead of aborting the invocation.
+pub fn check_version_signature(env: &Env, version: &CertificateVersion) -> Result<(), Error> {
+    let signature_matches = guard::signature_matches(
+        env,
+        &version.signing_key,
+        &version.content_hash.clone().into(),
+        &version.signature,
+    )?;
+    if !signature_matches {
+        return Err(Error::BadSignature);
+    }
     
-    // For now, since we're using a placeholder signature, return true
-    // In a real implementation, we would use the actual result
-    true
+    Ok(())
 }
 
-// Create the message that was signed
-fn create_verification_message(env: &Env, certificate: &Certificate) -> Bytes {
+// Create the message the issuer signs.
+// The certificate ID and issuance date are assigned by the ledger at issuance,
+// so the message only covers the content the issuer chooses, including the root
+// it committed over the metadata fields. Renewals also cover the certificate
+// they replace.
+pub fn create_verification_message(
+    env: &Env,
+    owner: &Address,
+    issuer: &Address,
+    cert_type_id: &String,
+    metadata: &CertificateMetadata,
+    metadata_root: Option<&BytesN<32>>,
+    expiration_date: Option<u64>,
+    predecessor: Option<&CertificateId>,
+) -> Bytes {
     // Combine certificate data to create the message
     let mut data = Bytes::new(env);
     
-    // Add certificate ID
-    data.append(&Bytes::from_slice(env, &certificate.id.0.to_array()));
-    
     // Add owner and issuer
-    data.append(&certificate.owner.clone().to_xdr(env));
-    data.append(&certificate.issuer.clone().to_xdr(env));
+    data.append(&owner.clone().to_xdr(env));
+    data.append(&issuer.clone().to_xdr(env));
+    
+    // Add the certification type
+    data.append(&cert_type_id.clone().to_xdr(env));
     
     // Add metadata - convert strings to bytes using to_xdr
-    data.append(&certificate.metadata.title.clone().to_xdr(env));
-    data.append(&certificate.metadata.description.clone().to_xdr(env));
-    data.append(&certificate.metadata.achievement_type.clone().to_xdr(env));
-    
-    // Add issuance date
-    let issuance = certificate.issuance_date;
-    let mut tmp = issuance;
-    for _ in 0..8 {
-        let byte = (tmp & 0xFF) as u8;
-        tmp >>= 8;
-        data.append(&Bytes::from_slice(env, &[byte]));
+    data.append(&metadata.title.clone().to_xdr(env));
+    data.append(&metadata.description.clone().to_xdr(env));
+    data.append(&metadata.achievement_type.clone().to_xdr(env));
+    data.append(&metadata.additional_data.clone().to_xdr(env));
+    
+    // Add the metadata root. Only certificates migrated from the legacy layout
+    // have none.
+    match metadata_root {
+        Some(root) => {
+            data.push_back(1);
+            data.append(&Bytes::from_array(env, &root.to_array()));
+        }
+        None => data.push_back(0),
     }
     
     // Add expiration date if present
-    if let Some(expiration) = certificate.expiration_date {
-        let mut tmp = expiration;
-        for _ in 0..8 {
-            let byte = (tmp & 0xFF) as u8;
-            tmp >>= 8;
-            data.append(&Bytes::from_slice(env, &[byte]));
+    match expiration_date {
+        Some(expiration) => {
+            data.push_back(1);
+            data.extend_from_array(&expiration.to_be_bytes());
         }
+        None => data.push_back(0),
+    }
+    
+    // Add the renewed certificate, if any
+    if let Some(predecessor) = predecessor {
+        data.append(&Bytes::from_array(env, &predecessor.0.to_array()));
     }
     
     // Hash the data to create the message
@@ -89,19 +164,18 @@ fn create_verification_message(env: &Env, certificate: &Certificate) -> Bytes {
     Bytes::from_slice(env, &hash.to_array())
 }
 
-// Get the issuer's public key (for signature verification)
-fn get_issuer_public_key(env: &Env, issuer: &Address) -> BytesN<32> {
-    // In a real implementation, we would fetch the issuer's public key
-    // from a registry or derive it from their address
-    // For now, we'll use the address's serialized form as a placeholder
-    let address_bytes = issuer.clone().to_xdr(env);
-    let hash = env.crypto().sha256(&address_bytes);
-    hash.into()
-}
-
-// Helper function to get a certificate key from a hash
-fn get_certificate_key(env: &Env, hash: &soroban_sdk::crypto::Hash<32>) -> Symbol {
-    // Create a simple key with the hash - avoid using colons
-    let key = format!("cert{:x}", hash.to_array()[0]);
-    Symbol::new(env, &key)
+// Create the message the issuer signs for an amendment: the verification
+// message of the new content, bound to the certificate ID and version number
+pub fn create_amendment_message(
+    env: &Env,
+    content_message: &Bytes,
+    certificate_id: &CertificateId,
+    version: u32,
+) -> Bytes {
+    let mut data = content_message.clone();
+    data.append(&Bytes::from_array(env, &certificate_id.0.to_array()));
+    data.extend_from_array(&version.to_be_bytes());
+    
+    let hash = env.crypto().sha256(&data);
+    Bytes::from_slice(env, &hash.to_array())
 }
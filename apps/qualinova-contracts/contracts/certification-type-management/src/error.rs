@@ -16,4 +16,7 @@ pub enum Error {
     InvalidStatus = 102,
     InvalidEvidence = 103,
     InvalidAuthority = 104,
+
+    // Specific to upgrades
+    SchemaVersionTooNew = 200,
 }
\ No newline at end of file
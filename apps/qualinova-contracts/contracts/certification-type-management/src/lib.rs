@@ -1,5 +1,5 @@
 #![no_std]
-use soroban_sdk::{contract, contractimpl, Env, String, Vec};
+use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, String, Vec};
 
 mod types;
 mod error;
@@ -56,6 +56,23 @@ pub trait CertificationTypeManagementTrait {
         cert_type_id: String,
         authority_id: String,
     ) -> Result<(), Error>;
+
+    fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), Error>;
+
+    fn migrate(env: Env) -> Result<u32, Error>;
+
+    fn get_schema_version(env: Env) -> u32;
+}
+
+#[contractimpl]
+impl CertificationTypeManagementContract {
+    // Sets the admin allowed to upgrade the contract as part of the deployment,
+    // so nobody can claim the contract between its deployment and a separate
+    // initialization call
+    pub fn __constructor(env: Env, admin: Address) {
+        let storage = Storage::new(&env);
+        storage.set_admin(admin);
+    }
 }
 
 #[contractimpl]
@@ -128,4 +145,22 @@ impl CertificationTypeManagementTrait for CertificationTypeManagementContract {
         let storage = Storage::new(&env);
         storage.assign_authority_to_cert_type(cert_type_id, authority_id)
     }
+
+    // Replaces the contract's code with an uploaded Wasm
+    fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        let storage = Storage::new(&env);
+        storage.upgrade(new_wasm_hash)
+    }
+
+    // Migrates stored data to the schema version of the current code
+    fn migrate(env: Env) -> Result<u32, Error> {
+        let storage = Storage::new(&env);
+        storage.migrate()
+    }
+
+    // Gets the schema version of the stored data
+    fn get_schema_version(env: Env) -> u32 {
+        let storage = Storage::new(&env);
+        storage.get_schema_version()
+    }
 }
\ No newline at end of file
@@ -1,4 +1,4 @@
-use soroban_sdk::{contracttype, vec, Env, String, Vec};
+use soroban_sdk::{contracttype, vec, Address, BytesN, Env, String, Vec};
 use crate::types::{CertTypeStatus, CertificationType};
 use crate::error::Error;
 
@@ -8,8 +8,14 @@ pub enum DataKey {
     CertificationType(String),  // cert_type_id -> CertificationType
     CertTypeIds,                // -> Vec<String>
     NextId,                     // -> u64
+    Admin,                      // -> Address
+    SchemaVersion,              // -> u32
 }
 
+// Version of the storage layout this build reads and writes
+pub const SCHEMA_VERSION: u32 = 1;
+const UNVERSIONED_SCHEMA_VERSION: u32 = 1;
+
 pub struct Storage<'a> {
     env: &'a Env,
 }
@@ -19,6 +25,52 @@ impl<'a> Storage<'a> {
         Self { env }
     }
 
+    // Set the admin allowed to upgrade the contract
+    pub fn set_admin(&self, admin: Address) {
+        self.env.storage().instance().set(&DataKey::Admin, &admin);
+        self.env.storage().instance().set(&DataKey::SchemaVersion, &SCHEMA_VERSION);
+    }
+
+    // Require the admin's authorization
+    fn require_admin(&self) -> Result<(), Error> {
+        let admin: Address = self
+            .env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::Unauthorized)?;
+        admin.require_auth();
+        Ok(())
+    }
+
+    // Replace the contract's code with an uploaded Wasm, keeping its stored data
+    pub fn upgrade(&self, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        self.require_admin()?;
+        self.env.deployer().update_current_contract_wasm(new_wasm_hash);
+        Ok(())
+    }
+
+    // Record the schema version of this build, refusing data written by newer code
+    pub fn migrate(&self) -> Result<u32, Error> {
+        self.require_admin()?;
+
+        if self.get_schema_version() > SCHEMA_VERSION {
+            return Err(Error::SchemaVersionTooNew);
+        }
+
+        self.env.storage().instance().set(&DataKey::SchemaVersion, &SCHEMA_VERSION);
+        Ok(SCHEMA_VERSION)
+    }
+
+    // Get the schema version of the stored data
+    pub fn get_schema_version(&self) -> u32 {
+        self.env
+            .storage()
+            .instance()
+            .get(&DataKey::SchemaVersion)
+            .unwrap_or(UNVERSIONED_SCHEMA_VERSION)
+    }
+
     // Get all certification type IDs
     fn get_cert_type_ids(&self) -> Vec<String> {
         self.env
@@ -1,14 +1,15 @@
 #![cfg(test)]
 #![allow(dead_code)]
 
-use soroban_sdk::{vec, Env, String};
-use crate::CertificationTypeManagementContract;
+use soroban_sdk::{testutils::Address as _, vec, Address, Bytes, BytesN, Env, String};
+use crate::storage::{DataKey, SCHEMA_VERSION};
+use crate::{CertificationTypeManagementContract, Error};
 use client::CertificationTypeManagementContractClient;
 
 #[test]
 fn test_register_certification_type() {
     let env = Env::default();
-    let contract_id = env.register(CertificationTypeManagementContract, ());
+    let contract_id = env.register(CertificationTypeManagementContract, (Address::generate(&env),));
     let client = CertificationTypeManagementContractClient::new(&env, &contract_id);
 
     let name = String::from_str(&env, "ISO_9001");
@@ -41,7 +42,7 @@ fn test_register_certification_type() {
 #[test]
 fn test_set_required_evidence() {
     let env = Env::default();
-    let contract_id = env.register(CertificationTypeManagementContract, ());
+    let contract_id = env.register(CertificationTypeManagementContract, (Address::generate(&env),));
     let client = CertificationTypeManagementContractClient::new(&env, &contract_id);
 
     // Register a certification type
@@ -78,7 +79,7 @@ fn test_set_required_evidence() {
 #[test]
 fn test_deprecate_certification_type() {
     let env = Env::default();
-    let contract_id = env.register(CertificationTypeManagementContract, ());
+    let contract_id = env.register(CertificationTypeManagementContract, (Address::generate(&env),));
     let client = CertificationTypeManagementContractClient::new(&env, &contract_id);
 
     // Register a certification type
@@ -112,7 +113,7 @@ fn test_deprecate_certification_type() {
 #[test]
 fn test_list_all_certification_types() {
     let env = Env::default();
-    let contract_id = env.register(CertificationTypeManagementContract, ());
+    let contract_id = env.register(CertificationTypeManagementContract, (Address::generate(&env),));
     let client = CertificationTypeManagementContractClient::new(&env, &contract_id);
 
     // Register multiple certification types
@@ -154,7 +155,7 @@ fn test_list_all_certification_types() {
 #[test]
 fn test_multiple_evidence_updates() {
     let env = Env::default();
-    let contract_id = env.register(CertificationTypeManagementContract, ());
+    let contract_id = env.register(CertificationTypeManagementContract, (Address::generate(&env),));
     let client = CertificationTypeManagementContractClient::new(&env, &contract_id);
 
     // Register a certification type
@@ -205,7 +206,7 @@ fn test_multiple_evidence_updates() {
 #[test]
 fn test_register_different_versions() {
     let env = Env::default();
-    let contract_id = env.register(CertificationTypeManagementContract, ());
+    let contract_id = env.register(CertificationTypeManagementContract, (Address::generate(&env),));
     let client = CertificationTypeManagementContractClient::new(&env, &contract_id);
 
     // Register a certification type with version 2015
@@ -263,8 +264,8 @@ fn test_register_different_versions() {
 // We need the client definition for the tests
 #[cfg(test)]
 mod client {
-    use soroban_sdk::{contractclient, Env, String, Vec};
-    use crate::CertificationType;
+    use soroban_sdk::{contractclient, BytesN, Env, String, Vec};
+    use crate::{CertificationType, Error};
 
     #[contractclient(name = "CertificationTypeManagementContractClient")]
     pub trait CertificationTypeManagementContractInterface {
@@ -308,5 +309,38 @@ mod client {
             cert_type_id: String,
             authority_id: String,
         );
+
+        fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), Error>;
+
+        fn migrate(env: Env) -> Result<u32, Error>;
+
+        fn get_schema_version(env: Env) -> u32;
     }
+}
+
+#[test]
+fn test_upgrade_and_migrate() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(CertificationTypeManagementContract, (admin.clone(),));
+    let client = CertificationTypeManagementContractClient::new(&env, &contract_id);
+
+    // A test contract runs the empty Wasm, so upgrading to it succeeds, but
+    // only with the admin's authorization
+    let wasm_hash: BytesN<32> = env.crypto().sha256(&Bytes::new(&env)).into();
+    env.set_auths(&[]);
+    assert!(client.try_upgrade(&wasm_hash).is_err());
+    env.mock_all_auths();
+    client.upgrade(&wasm_hash);
+    assert_eq!(env.auths()[0].0, admin);
+
+    // A new deployment starts at the current schema version
+    assert_eq!(client.get_schema_version(), SCHEMA_VERSION);
+    assert_eq!(client.migrate(), SCHEMA_VERSION);
+
+    // Code older than the stored data refuses to migrate it
+    env.as_contract(&contract_id, || {
+        env.storage().instance().set(&DataKey::SchemaVersion, &(SCHEMA_VERSION + 1));
+    });
+    assert_eq!(client.try_migrate(), Err(Ok(Error::SchemaVersionTooNew)));
 }
\ No newline at end of file
@@ -1,5 +1,9 @@
 use crate::types::*;
-use soroban_sdk::{Address, Env, Vec};
+use soroban_sdk::{Address, BytesN, Env, Symbol, Vec};
+
+// Version of the storage layout this build reads and writes
+pub const SCHEMA_VERSION: u32 = 1;
+const UNVERSIONED_SCHEMA_VERSION: u32 = 1;
 
 pub fn initialize(env: Env, admin: Address) {
     admin.require_auth();
@@ -19,6 +23,9 @@ pub fn initialize(env: Env, admin: Address) {
     env.storage()
         .instance()
         .set(&DataKey::Admin, &admin);
+
+    // Record the schema version of the empty storage
+    set_schema_version(&env);
 }
 
 pub fn get_admin(env: Env) -> Address {
@@ -40,7 +47,118 @@ pub fn require_admin(env: Env, address: Address) {
     }
 }
 
-pub fn transfer_admin(env: Env, current_admin: Address, new_admin: Address) {
-    require_admin(env.clone(), current_admin);
-    env.storage().instance().set(&DataKey::Admin, &new_admin);
-}
\ No newline at end of file
+pub fn propose_admin(
+    env: Env,
+    current_admin: Address,
+    new_admin: Address,
+    expires_at: Option<u64>,
+) {
+    require_admin(env.clone(), current_admin.clone());
+    if new_admin == current_admin {
+        panic!("New admin must differ from the current admin");
+    }
+
+    let proposed_at = env.ledger().timestamp();
+    if expires_at.is_some_and(|expires_at| expires_at <= proposed_at) {
+        panic!("Proposal expiry must be in the future");
+    }
+
+    // A new proposal replaces any pending one
+    let proposal = AdminProposal {
+        proposed_admin: new_admin.clone(),
+        proposed_at,
+        expires_at,
+    };
+    env.storage()
+        .instance()
+        .set(&DataKey::AdminProposal, &proposal);
+
+    env.events().publish(
+        (Symbol::new(&env, "admin_proposed"),),
+        (current_admin, new_admin, expires_at),
+    );
+}
+
+pub fn accept_admin(env: Env) {
+    let proposal = get_admin_proposal(env.clone());
+    proposal.proposed_admin.require_auth();
+
+    if proposal
+        .expires_at
+        .is_some_and(|expires_at| env.ledger().timestamp() >= expires_at)
+    {
+        panic!("Admin proposal expired");
+    }
+
+    let previous_admin = get_admin(env.clone());
+    env.storage().instance().remove(&DataKey::AdminProposal);
+    env.storage()
+        .instance()
+        .set(&DataKey::Admin, &proposal.proposed_admin);
+
+    env.events().publish(
+        (Symbol::new(&env, "admin_transferred"),),
+        (previous_admin, proposal.proposed_admin),
+    );
+}
+
+pub fn cancel_admin_proposal(env: Env, current_admin: Address) {
+    require_admin(env.clone(), current_admin.clone());
+    let proposal = get_admin_proposal(env.clone());
+    env.storage().instance().remove(&DataKey::AdminProposal);
+
+    env.events().publish(
+        (Symbol::new(&env, "admin_proposal_cancelled"),),
+        (current_admin, proposal.proposed_admin),
+    );
+}
+
+pub fn get_admin_proposal(env: Env) -> AdminProposal {
+    env.storage()
+        .instance()
+        .get(&DataKey::AdminProposal)
+        .unwrap_or_else(|| panic!("No pending admin proposal"))
+}
+
+pub fn set_certification_contract(env: Env, admin: Address, certification_contract: Address) {
+    require_admin(env.clone(), admin);
+    env.storage()
+        .instance()
+        .set(&DataKey::CertificationContract, &certification_contract);
+}
+
+pub fn get_certification_contract(env: Env) -> Address {
+    env.storage()
+        .instance()
+        .get(&DataKey::CertificationContract)
+        .unwrap_or_else(|| panic!("Certification contract not set"))
+}
+
+pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
+    require_admin(env.clone(), get_admin(env.clone()));
+    env.deployer().update_current_contract_wasm(new_wasm_hash);
+}
+
+pub fn migrate(env: Env) -> u32 {
+    require_admin(env.clone(), get_admin(env.clone()));
+
+    if get_schema_version(env.clone()) > SCHEMA_VERSION {
+        panic!("Stored data has a newer schema version");
+    }
+
+    set_schema_version(&env);
+    SCHEMA_VERSION
+}
+
+pub fn get_schema_version(env: Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::SchemaVersion)
+        .unwrap_or(UNVERSIONED_SCHEMA_VERSION)
+}
+
+fn set_schema_version(env: &Env) {
+    env.storage()
+        .instance()
+        .set(&DataKey::SchemaVersion, &SCHEMA_VERSION);
+}
@@ -1,3 +1,4 @@
+use crate::admin;
 use crate::types::*;
 use soroban_sdk::{Address, Env, String, Vec};
 
@@ -171,6 +172,9 @@ pub fn deactivate_entity(env: Env, entity_id: Address, _reason: String) {
 }
 
 pub fn add_certification(env: Env, entity_id: Address, certification_id: String) {
+    // Only the certification issuance contract can record certifications
+    admin::get_certification_contract(env.clone()).require_auth();
+
     let mut entity: Entity = env
         .storage()
         .instance()
@@ -1,5 +1,5 @@
 #![no_std]
-use soroban_sdk::{Address, Env, String, Vec, contract, contractimpl};
+use soroban_sdk::{Address, BytesN, Env, String, Vec, contract, contractimpl};
 
 mod admin;
 mod entity;
@@ -54,6 +54,10 @@ impl CertifiableEntity {
         entity::deactivate_entity(env, entity_id, reason)
     }
 
+    pub fn add_certification(env: Env, entity_id: Address, certification_id: String) {
+        entity::add_certification(env, entity_id, certification_id)
+    }
+
     pub fn list_entity_certifications(env: Env, entity_id: Address) -> Vec<String> {
         entity::list_entity_certifications(env, entity_id)
     }
@@ -67,7 +71,45 @@ impl CertifiableEntity {
         admin::get_admin(env)
     }
 
-    pub fn transfer_admin(env: Env, current_admin: Address, new_admin: Address) {
-        admin::transfer_admin(env, current_admin, new_admin)
+    pub fn propose_admin(
+        env: Env,
+        current_admin: Address,
+        new_admin: Address,
+        expires_at: Option<u64>,
+    ) {
+        admin::propose_admin(env, current_admin, new_admin, expires_at)
+    }
+
+    pub fn accept_admin(env: Env) {
+        admin::accept_admin(env)
+    }
+
+    pub fn cancel_admin_proposal(env: Env, current_admin: Address) {
+        admin::cancel_admin_proposal(env, current_admin)
+    }
+
+    pub fn get_admin_proposal(env: Env) -> AdminProposal {
+        admin::get_admin_proposal(env)
+    }
+
+    pub fn set_certification_contract(env: Env, admin: Address, certification_contract: Address) {
+        admin::set_certification_contract(env, admin, certification_contract)
+    }
+
+    pub fn get_certification_contract(env: Env) -> Address {
+        admin::get_certification_contract(env)
+    }
+
+    // Upgrade functions
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
+        admin::upgrade(env, new_wasm_hash)
+    }
+
+    pub fn migrate(env: Env) -> u32 {
+        admin::migrate(env)
+    }
+
+    pub fn get_schema_version(env: Env) -> u32 {
+        admin::get_schema_version(env)
     }
 }
\ No newline at end of file
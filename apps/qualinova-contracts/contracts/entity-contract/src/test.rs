@@ -2,8 +2,11 @@
 extern crate std;
 
 use crate::types::*;
-use crate::{CertifiableEntity, CertifiableEntityClient};
-use soroban_sdk::{Address, Env, String, testutils::Address as _};
+use crate::{CertifiableEntity, CertifiableEntityClient, SCHEMA_VERSION};
+use soroban_sdk::{
+    Address, Bytes, BytesN, Env, String,
+    testutils::{Address as _, Ledger},
+};
 
 #[test]
 fn test_initialize_contract() {
@@ -219,4 +222,156 @@ fn test_deactivate_inactive_entity() {
 
     // Second deactivation - should panic
     contract_client.deactivate_entity(&public_key, &String::from_str(&env, "Another reason"));
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_add_certification() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let public_key = Address::generate(&env);
+    let certification_contract = Address::generate(&env);
+
+    let contract_address = env.register(CertifiableEntity, ());
+    let contract_client = CertifiableEntityClient::new(&env, &contract_address);
+
+    env.mock_all_auths();
+    contract_client.initialize(&admin);
+    contract_client.set_certification_contract(&admin, &certification_contract);
+
+    contract_client.register_entity(
+        &String::from_str(&env, "Acme Manufacturing Corp"),
+        &String::from_str(&env, "Automotive"),
+        &String::from_str(&env, "San Francisco, CA"),
+        &String::from_str(&env, "contact@acmecorp.com"),
+        &public_key,
+    );
+
+    let certification_id = String::from_str(&env, "CERT_1");
+    contract_client.add_certification(&public_key, &certification_id);
+
+    // The certification contract must authorize the call
+    assert_eq!(env.auths()[0].0, certification_contract);
+
+    let certifications = contract_client.list_entity_certifications(&public_key);
+    assert_eq!(certifications.len(), 1);
+    assert_eq!(certifications.get(0).unwrap(), certification_id);
+}
+
+#[test]
+#[should_panic(expected = "Certification contract not set")]
+fn test_add_certification_without_certification_contract() {
+    let env = Env::default();
+    let public_key = Address::generate(&env);
+
+    let contract_address = env.register(CertifiableEntity, ());
+    let contract_client = CertifiableEntityClient::new(&env, &contract_address);
+
+    env.mock_all_auths();
+    contract_client.initialize(&Address::generate(&env));
+
+    contract_client.register_entity(
+        &String::from_str(&env, "Acme Manufacturing Corp"),
+        &String::from_str(&env, "Automotive"),
+        &String::from_str(&env, "San Francisco, CA"),
+        &String::from_str(&env, "contact@acmecorp.com"),
+        &public_key,
+    );
+
+    contract_client.add_certification(&public_key, &String::from_str(&env, "CERT_1"));
+}
+
+#[test]
+fn test_upgrade_and_migrate() {
+    let env = Env::default();
+    let contract_address = env.register(CertifiableEntity, ());
+    let contract_client = CertifiableEntityClient::new(&env, &contract_address);
+
+    let admin = Address::generate(&env);
+    env.mock_all_auths();
+    contract_client.initialize(&admin);
+
+    // A new deployment starts at the current schema version
+    assert_eq!(contract_client.get_schema_version(), SCHEMA_VERSION);
+    assert_eq!(contract_client.migrate(), SCHEMA_VERSION);
+
+    // A test contract runs the empty Wasm, so upgrading to it succeeds, but
+    // only with the admin's authorization
+    let wasm_hash: BytesN<32> = env.crypto().sha256(&Bytes::new(&env)).into();
+    env.set_auths(&[]);
+    assert!(contract_client.try_upgrade(&wasm_hash).is_err());
+    env.mock_all_auths();
+    contract_client.upgrade(&wasm_hash);
+    assert_eq!(env.auths()[0].0, admin);
+
+    // Only code that was uploaded can be installed
+    let unknown_hash = BytesN::from_array(&env, &[1u8; 32]);
+    assert!(contract_client.try_upgrade(&unknown_hash).is_err());
+}
+
+#[test]
+#[should_panic(expected = "Stored data has a newer schema version")]
+fn test_migrate_newer_schema() {
+    let env = Env::default();
+    let contract_address = env.register(CertifiableEntity, ());
+    let contract_client = CertifiableEntityClient::new(&env, &contract_address);
+
+    env.mock_all_auths();
+    contract_client.initialize(&Address::generate(&env));
+
+    // Code older than the stored data refuses to migrate it
+    env.as_contract(&contract_address, || {
+        env.storage()
+            .instance()
+            .set(&DataKey::SchemaVersion, &(SCHEMA_VERSION + 1));
+    });
+    contract_client.migrate();
+}
+
+#[test]
+fn test_propose_and_accept_admin() {
+    let env = Env::default();
+    let contract_address = env.register(CertifiableEntity, ());
+    let contract_client = CertifiableEntityClient::new(&env, &contract_address);
+    let admin = Address::generate(&env);
+    let new_admin = Address::generate(&env);
+
+    env.mock_all_auths();
+    contract_client.initialize(&admin);
+
+    // The role stays with the current admin until the proposal is accepted
+    contract_client.propose_admin(&admin, &new_admin, &None);
+    assert_eq!(contract_client.get_admin(), admin);
+    assert_eq!(contract_client.get_admin_proposal().proposed_admin, new_admin);
+
+    // Accepting needs the new admin's authorization
+    contract_client.accept_admin();
+    assert_eq!(
+        env.auths().iter().map(|(address, _)| address.clone()).collect::<std::vec::Vec<_>>(),
+        std::vec![new_admin.clone()]
+    );
+    assert_eq!(contract_client.get_admin(), new_admin);
+    assert!(contract_client.try_get_admin_proposal().is_err());
+
+    // A cancelled proposal can no longer be accepted
+    contract_client.propose_admin(&new_admin, &admin, &None);
+    contract_client.cancel_admin_proposal(&new_admin);
+    assert!(contract_client.try_accept_admin().is_err());
+    assert_eq!(contract_client.get_admin(), new_admin);
+}
+
+#[test]
+#[should_panic(expected = "Admin proposal expired")]
+fn test_accept_expired_admin_proposal() {
+    let env = Env::default();
+    let contract_address = env.register(CertifiableEntity, ());
+    let contract_client = CertifiableEntityClient::new(&env, &contract_address);
+    let admin = Address::generate(&env);
+
+    env.mock_all_auths();
+    contract_client.initialize(&admin);
+    env.ledger().with_mut(|ledger| ledger.timestamp = 1000);
+    contract_client.propose_admin(&admin, &Address::generate(&env), &Some(2000));
+
+    env.ledger().with_mut(|ledger| ledger.timestamp = 2000);
+    contract_client.accept_admin();
+}
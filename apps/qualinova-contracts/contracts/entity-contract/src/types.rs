@@ -6,6 +6,9 @@ pub enum DataKey {
     EntityIds,             // List of all entity IDs (public keys)
     NextEntityId,          // Counter for generating unique entity IDs
     Admin,                 // Admin address
+    CertificationContract, // Issuance contract allowed to record certifications
+    SchemaVersion,         // Version of the storage layout
+    AdminProposal,         // Pending handover of the admin role
 }
 
 #[contracttype]
@@ -22,6 +25,14 @@ pub struct Entity {
     pub certifications: Vec<String>,   // List of certification IDs associated with this entity
 }
 
+#[contracttype]
+#[derive(Clone, PartialEq, Debug)]
+pub struct AdminProposal {
+    pub proposed_admin: Address,       // Address that takes over once it accepts
+    pub proposed_at: u64,              // Timestamp of the proposal
+    pub expires_at: Option<u64>,       // Timestamp from which it can no longer be accepted
+}
+
 #[contracttype]
 #[derive(Clone, PartialEq, Debug)]
 pub enum EntityStatus {
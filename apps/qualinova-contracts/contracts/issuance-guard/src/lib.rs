@@ -0,0 +1,29 @@
+#![no_std]
+use soroban_sdk::{contract, contractimpl, Address, Bytes, BytesN, Env, IntoVal};
+
+mod test;
+
+// Checks the certification contract cannot run itself. The host aborts the
+// invocation when one of these checks fails, so the certification contract
+// calls them here and sees a failed call instead of being aborted.
+#[contract]
+pub struct IssuanceGuard;
+
+#[contractimpl]
+impl IssuanceGuard {
+    // Verify an ed25519 signature over a message; aborts if it does not match
+    pub fn verify_signature(
+        env: Env,
+        public_key: BytesN<32>,
+        message: Bytes,
+        signature: BytesN<64>,
+    ) {
+        env.crypto().ed25519_verify(&public_key, &message, &signature);
+    }
+
+    // Require the address to authorize the content with the given hash; aborts
+    // without its authorization
+    pub fn authorize(env: Env, address: Address, content_hash: BytesN<32>) {
+        address.require_auth_for_args((content_hash,).into_val(&env));
+    }
+}
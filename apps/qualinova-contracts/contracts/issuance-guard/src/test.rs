@@ -0,0 +1,52 @@
+#![cfg(test)]
+extern crate std;
+
+use crate::{IssuanceGuard, IssuanceGuardClient};
+use ed25519_dalek::{Signer, SigningKey};
+use soroban_sdk::testutils::{Address as _, MockAuth, MockAuthInvoke};
+use soroban_sdk::{Address, Bytes, BytesN, Env, IntoVal};
+
+#[test]
+fn test_verify_signature() {
+    let env = Env::default();
+    let contract_address = env.register(IssuanceGuard, ());
+    let client = IssuanceGuardClient::new(&env, &contract_address);
+
+    let signing_key = SigningKey::from_bytes(&[3u8; 32]);
+    let public_key = BytesN::from_array(&env, &signing_key.verifying_key().to_bytes());
+    let message = Bytes::from_slice(&env, b"certificate");
+    let signature = BytesN::from_array(&env, &signing_key.sign(b"certificate").to_bytes());
+
+    // A matching signature passes
+    client.verify_signature(&public_key, &message, &signature);
+
+    // Any other message fails the call
+    let other_message = Bytes::from_slice(&env, b"tampered");
+    assert!(client.try_verify_signature(&public_key, &other_message, &signature).is_err());
+}
+
+#[test]
+fn test_authorize() {
+    let env = Env::default();
+    let contract_address = env.register(IssuanceGuard, ());
+    let client = IssuanceGuardClient::new(&env, &contract_address);
+
+    let owner = Address::generate(&env);
+    let content_hash = BytesN::from_array(&env, &[7u8; 32]);
+
+    // Without the address's authorization the call fails
+    assert!(client.try_authorize(&owner, &content_hash).is_err());
+
+    // The authorization covers the content hash
+    client
+        .mock_auths(&[MockAuth {
+            address: &owner,
+            invoke: &MockAuthInvoke {
+                contract: &contract_address,
+                fn_name: "authorize",
+                args: (content_hash.clone(),).into_val(&env),
+                sub_invokes: &[],
+            },
+        }])
+        .authorize(&owner, &content_hash);
+}
@@ -0,0 +1,67 @@
+use crate::credential::{
+    account_uri, check_exportable, credential_id, credential_status, issuer_object, set_validity,
+    VC_CONTEXT,
+};
+use crate::{Authority, Certificate, CertificationContract, Error, IssuerProfile};
+use serde_json::{json, Value};
+
+// JSON-LD context of Open Badges 3.0
+pub const OB_CONTEXT: &str = "https://purl.imsglobal.org/spec/ob/v3p0/context-3.0.3.json";
+
+// Convert a certificate into an unsecured Open Badges 3.0 credential. The
+// certification type becomes the achievement, so every certificate of a type
+// shares one achievement ID.
+pub fn to_open_badge(
+    certificate: &Certificate,
+    authority: &Authority,
+    issuer: &IssuerProfile,
+    contract: &CertificationContract,
+) -> Result<Value, Error> {
+    check_exportable(certificate, authority)?;
+    
+    let metadata = &certificate.metadata;
+    let achievement = json!({
+        "id": achievement_id(contract, &certificate.cert_type_id),
+        "type": ["Achievement"],
+        "achievementType": "Certification",
+        "name": metadata.achievement_type,
+        "description": metadata.description,
+        "criteria": {
+            "narrative": format!(
+                "{} certification issued by {}",
+                metadata.achievement_type, authority.name
+            ),
+        },
+    });
+    
+    let mut credential = json!({
+        "@context": [VC_CONTEXT, OB_CONTEXT],
+        "id": credential_id(certificate),
+        "type": ["VerifiableCredential", "OpenBadgeCredential"],
+        "name": metadata.title,
+        "issuer": issuer_object(certificate, authority, issuer, contract, true),
+        "credentialSubject": {
+            "id": account_uri(contract, &certificate.owner),
+            "type": ["AchievementSubject"],
+            "achievement": achievement,
+        },
+        "credentialStatus": credential_status(certificate, contract),
+    });
+    set_validity(&mut credential, certificate)?;
+    
+    Ok(credential)
+}
+
+// Get the achievement ID of a certification type, percent-encoding the type ID
+fn achievement_id(contract: &CertificationContract, cert_type_id: &str) -> String {
+    let mut id = format!("{}#type:", account_uri(contract, &contract.contract_id));
+    for byte in cert_type_id.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~".contains(&byte) {
+            id.push(byte as char);
+        } else {
+            id.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    
+    id
+}
@@ -0,0 +1,154 @@
+use crate::{
+    Authority, Certificate, CertificateStatus, CertificationContract, Error, IssuerProfile,
+};
+use chrono::{DateTime, SecondsFormat};
+use serde_json::{json, Map, Value};
+
+// JSON-LD context of the W3C Verifiable Credentials Data Model 2.0
+pub const VC_CONTEXT: &str = "https://www.w3.org/ns/credentials/v2";
+
+// Status entry type pointing at the certification contract
+pub const STATUS_TYPE: &str = "SorobanCertificateStatus";
+
+// Contract function that reports a certificate's current status
+pub const STATUS_FUNCTION: &str = "get_certificate_status";
+
+// Convert a certificate into an unsecured W3C Verifiable Credential
+pub fn to_verifiable_credential(
+    certificate: &Certificate,
+    authority: &Authority,
+    issuer: &IssuerProfile,
+    contract: &CertificationContract,
+) -> Result<Value, Error> {
+    check_exportable(certificate, authority)?;
+    
+    let mut subject = Map::new();
+    subject.insert(
+        "id".into(),
+        json!(account_uri(contract, &certificate.owner)),
+    );
+    subject.insert("certificateId".into(), json!(certificate.id));
+    subject.insert("certificationType".into(), json!(certificate.cert_type_id));
+    subject.insert("title".into(), json!(certificate.metadata.title));
+    subject.insert(
+        "description".into(),
+        json!(certificate.metadata.description),
+    );
+    subject.insert(
+        "achievementType".into(),
+        json!(certificate.metadata.achievement_type),
+    );
+    if !certificate.metadata.additional_data.is_empty() {
+        subject.insert(
+            "additionalData".into(),
+            json!(certificate.metadata.additional_data),
+        );
+    }
+    
+    let mut credential = json!({
+        "@context": [VC_CONTEXT],
+        "id": credential_id(certificate),
+        "type": ["VerifiableCredential"],
+        "name": certificate.metadata.title,
+        "issuer": issuer_object(certificate, authority, issuer, contract, false),
+        "credentialSubject": subject,
+        "credentialStatus": credential_status(certificate, contract),
+    });
+    set_validity(&mut credential, certificate)?;
+    
+    Ok(credential)
+}
+
+// Check that the certificate can be exported with the authority's data
+pub(crate) fn check_exportable(
+    certificate: &Certificate,
+    authority: &Authority,
+) -> Result<(), Error> {
+    if certificate.status != CertificateStatus::Active {
+        return Err(Error::CertificateNotActive(certificate.status));
+    }
+    
+    // Authorities are keyed by their public key, which is the issuer's address
+    if authority.public_key != certificate.issuer {
+        return Err(Error::IssuerMismatch);
+    }
+    
+    Ok(())
+}
+
+// Get the credential ID of a certificate
+pub(crate) fn credential_id(certificate: &Certificate) -> String {
+    format!("urn:qualinova:certificate:{}", certificate.id)
+}
+
+// Get the CAIP-10 URI of a Stellar account or contract on the contract's network
+pub(crate) fn account_uri(contract: &CertificationContract, address: &str) -> String {
+    format!("stellar:{}:{}", contract.network, address)
+}
+
+// Build the issuer object, named after the authority. Open Badges also requires
+// the issuer to be typed as a profile.
+pub(crate) fn issuer_object(
+    certificate: &Certificate,
+    authority: &Authority,
+    issuer: &IssuerProfile,
+    contract: &CertificationContract,
+    profile: bool,
+) -> Value {
+    let mut object = Map::new();
+    let id = issuer
+        .id
+        .clone()
+        .unwrap_or_else(|| account_uri(contract, &certificate.issuer));
+    object.insert("id".into(), json!(id));
+    if profile {
+        object.insert("type".into(), json!(["Profile"]));
+    }
+    object.insert("name".into(), json!(authority.name));
+    if let Some(url) = &issuer.url {
+        object.insert("url".into(), json!(url));
+    }
+    if let Some(email) = &issuer.email {
+        object.insert("email".into(), json!(email));
+    }
+    if !authority.accreditation_info.is_empty() {
+        object.insert("accreditation".into(), json!(authority.accreditation_info));
+    }
+    
+    Value::Object(object)
+}
+
+// Build the status entry that points back to the certificate on the contract
+pub(crate) fn credential_status(
+    certificate: &Certificate,
+    contract: &CertificationContract,
+) -> Value {
+    let contract_uri = account_uri(contract, &contract.contract_id);
+    
+    json!({
+        "id": format!("{}#{}", contract_uri, certificate.id),
+        "type": STATUS_TYPE,
+        "statusContract": contract_uri,
+        "statusFunction": STATUS_FUNCTION,
+        "certificateId": certificate.id,
+    })
+}
+
+// Set the credential's validity period from the certificate's issuance and expiration dates
+pub(crate) fn set_validity(credential: &mut Value, certificate: &Certificate) -> Result<(), Error> {
+    credential["validFrom"] = json!(timestamp(certificate.issuance_date)?);
+    if let Some(expiration_date) = certificate.expiration_date {
+        credential["validUntil"] = json!(timestamp(expiration_date)?);
+    }
+    
+    Ok(())
+}
+
+// Format a ledger timestamp as an ISO 8601 date-time in UTC
+pub(crate) fn timestamp(seconds: u64) -> Result<String, Error> {
+    i64::try_from(seconds)
+        .ok()
+        .and_then(|seconds| DateTime::from_timestamp(seconds, 0))
+        .map(|date| date.to_rfc3339_opts(SecondsFormat::Secs, true))
+        .ok_or(Error::InvalidTimestamp(seconds))
+}
@@ -0,0 +1,26 @@
+use crate::CertificateStatus;
+use std::fmt;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Error {
+    // The certificate is not active, so it cannot be exported as a credential
+    CertificateNotActive(CertificateStatus),
+    // The authority is not the certificate's issuer
+    IssuerMismatch,
+    // A date does not fit in an ISO 8601 timestamp
+    InvalidTimestamp(u64),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::CertificateNotActive(status) => {
+                write!(f, "certificate is {:?}, not Active", status)
+            }
+            Error::IssuerMismatch => write!(f, "authority is not the certificate's issuer"),
+            Error::InvalidTimestamp(timestamp) => write!(f, "invalid timestamp {}", timestamp),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
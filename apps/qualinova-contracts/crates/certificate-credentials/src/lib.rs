@@ -0,0 +1,18 @@
+// Off-chain export of certificates from the certification contract as W3C
+// Verifiable Credentials and Open Badges 3.0
+
+mod badge;
+mod credential;
+mod error;
+mod types;
+
+pub use badge::{to_open_badge, OB_CONTEXT};
+pub use credential::{to_verifiable_credential, STATUS_FUNCTION, STATUS_TYPE, VC_CONTEXT};
+pub use error::Error;
+pub use types::{
+    Authority, AuthorityStatus, Certificate, CertificateMetadata, CertificateStatus,
+    CertificationContract, IssuerProfile,
+};
+
+#[cfg(test)]
+mod test;
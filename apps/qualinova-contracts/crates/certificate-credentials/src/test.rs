@@ -0,0 +1,192 @@
+use crate::*;
+use serde_json::json;
+use std::collections::BTreeMap;
+
+const ISSUER: &str = "GBISSUERACCOUNT";
+const OWNER: &str = "GBOWNERACCOUNT";
+const CERT_ID: &str = "0101010101010101010101010101010101010101010101010101010101010101";
+
+fn create_certificate() -> Certificate {
+    let mut additional_data = BTreeMap::new();
+    additional_data.insert("course_id".to_string(), "02".repeat(32));
+    
+    Certificate {
+        id: CERT_ID.to_string(),
+        owner: OWNER.to_string(),
+        issuer: ISSUER.to_string(),
+        cert_type_id: "ISO 9001".to_string(),
+        metadata: CertificateMetadata {
+            title: "Quality Management System".to_string(),
+            description: "Certified quality management system".to_string(),
+            achievement_type: "ISO 9001".to_string(),
+            additional_data,
+        },
+        issuance_date: 1_700_000_000,
+        expiration_date: Some(1_794_694_400),
+        status: CertificateStatus::Active,
+        signature: "03".repeat(64),
+        signing_key: "04".repeat(32),
+    }
+}
+
+fn create_authority() -> Authority {
+    Authority {
+        authority_id: "AUTH001".to_string(),
+        name: "Certifying Authority".to_string(),
+        public_key: ISSUER.to_string(),
+        registration_date: 1_600_000_000,
+        accreditation_info: "ISO/IEC 17021-1".to_string(),
+        allowed_cert_types: vec!["ISO 9001".to_string()],
+        status: AuthorityStatus::Active,
+    }
+}
+
+fn create_contract() -> CertificationContract {
+    CertificationContract {
+        network: "testnet".to_string(),
+        contract_id: "CCERTIFICATION".to_string(),
+    }
+}
+
+#[test]
+fn test_verifiable_credential() {
+    let issuer = IssuerProfile {
+        url: Some("https://authority.example".to_string()),
+        ..Default::default()
+    };
+    let credential = to_verifiable_credential(
+        &create_certificate(),
+        &create_authority(),
+        &issuer,
+        &create_contract(),
+    )
+    .unwrap();
+    
+    assert_eq!(credential["@context"], json!([VC_CONTEXT]));
+    assert_eq!(
+        credential["id"],
+        format!("urn:qualinova:certificate:{}", CERT_ID)
+    );
+    assert_eq!(credential["type"], json!(["VerifiableCredential"]));
+    assert_eq!(credential["validFrom"], "2023-11-14T22:13:20Z");
+    assert_eq!(credential["validUntil"], "2026-11-14T22:13:20Z");
+    assert_eq!(
+        credential["issuer"],
+        json!({
+            "id": "stellar:testnet:GBISSUERACCOUNT",
+            "name": "Certifying Authority",
+            "url": "https://authority.example",
+            "accreditation": "ISO/IEC 17021-1",
+        })
+    );
+    
+    let subject = &credential["credentialSubject"];
+    assert_eq!(subject["id"], "stellar:testnet:GBOWNERACCOUNT");
+    assert_eq!(subject["certificationType"], "ISO 9001");
+    assert_eq!(subject["achievementType"], "ISO 9001");
+    assert_eq!(subject["additionalData"]["course_id"], "02".repeat(32));
+    
+    assert_eq!(
+        credential["credentialStatus"],
+        json!({
+            "id": format!("stellar:testnet:CCERTIFICATION#{}", CERT_ID),
+            "type": STATUS_TYPE,
+            "statusContract": "stellar:testnet:CCERTIFICATION",
+            "statusFunction": STATUS_FUNCTION,
+            "certificateId": CERT_ID,
+        })
+    );
+}
+
+#[test]
+fn test_open_badge() {
+    let mut certificate = create_certificate();
+    certificate.expiration_date = None;
+    let issuer = IssuerProfile {
+        id: Some("did:web:authority.example".to_string()),
+        ..Default::default()
+    };
+    let badge = to_open_badge(
+        &certificate,
+        &create_authority(),
+        &issuer,
+        &create_contract(),
+    )
+    .unwrap();
+    
+    assert_eq!(badge["@context"], json!([VC_CONTEXT, OB_CONTEXT]));
+    assert_eq!(
+        badge["type"],
+        json!(["VerifiableCredential", "OpenBadgeCredential"])
+    );
+    assert_eq!(badge["issuer"]["id"], "did:web:authority.example");
+    assert_eq!(badge["issuer"]["type"], json!(["Profile"]));
+    assert_eq!(badge["validFrom"], "2023-11-14T22:13:20Z");
+    assert!(badge.get("validUntil").is_none());
+    
+    let subject = &badge["credentialSubject"];
+    assert_eq!(subject["type"], json!(["AchievementSubject"]));
+    let achievement = &subject["achievement"];
+    assert_eq!(
+        achievement["id"],
+        "stellar:testnet:CCERTIFICATION#type:ISO%209001"
+    );
+    assert_eq!(achievement["achievementType"], "Certification");
+    assert_eq!(achievement["name"], "ISO 9001");
+    assert_eq!(
+        achievement["criteria"]["narrative"],
+        "ISO 9001 certification issued by Certifying Authority"
+    );
+    assert_eq!(badge["credentialStatus"]["type"], STATUS_TYPE);
+}
+
+#[test]
+fn test_export_errors() {
+    let issuer = IssuerProfile::default();
+    let contract = create_contract();
+    
+    let mut certificate = create_certificate();
+    certificate.status = CertificateStatus::Withdrawn;
+    assert_eq!(
+        to_verifiable_credential(&certificate, &create_authority(), &issuer, &contract),
+        Err(Error::CertificateNotActive(CertificateStatus::Withdrawn))
+    );
+    
+    let mut authority = create_authority();
+    authority.public_key = "GBOTHERACCOUNT".to_string();
+    assert_eq!(
+        to_open_badge(&create_certificate(), &authority, &issuer, &contract),
+        Err(Error::IssuerMismatch)
+    );
+    
+    let mut certificate = create_certificate();
+    certificate.expiration_date = Some(u64::MAX);
+    assert_eq!(
+        to_verifiable_credential(&certificate, &create_authority(), &issuer, &contract),
+        Err(Error::InvalidTimestamp(u64::MAX))
+    );
+}
+
+#[test]
+fn test_certificate_from_json() {
+    let certificate: Certificate = serde_json::from_value(json!({
+        "id": CERT_ID,
+        "owner": OWNER,
+        "issuer": ISSUER,
+        "cert_type_id": "ISO 9001",
+        "metadata": {
+            "title": "Quality Management System",
+            "description": "Certified quality management system",
+            "achievement_type": "ISO 9001",
+            "additional_data": { "course_id": "02".repeat(32) },
+        },
+        "issuance_date": 1_700_000_000u64,
+        "expiration_date": 1_794_694_400u64,
+        "status": "Active",
+        "signature": "03".repeat(64),
+        "signing_key": "04".repeat(32),
+    }))
+    .unwrap();
+    
+    assert_eq!(certificate, create_certificate());
+}
@@ -0,0 +1,71 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+// Certificate as returned by the certification contract's `get_certificate`.
+// Byte fields are hex-encoded and addresses are strkeys.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Certificate {
+    pub id: String,
+    pub owner: String,
+    pub issuer: String,
+    pub cert_type_id: String,
+    pub metadata: CertificateMetadata,
+    pub issuance_date: u64,
+    pub expiration_date: Option<u64>,
+    pub status: CertificateStatus,
+    pub signature: String,
+    pub signing_key: String,
+}
+
+// Certificate metadata, with the additional data hashes hex-encoded
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct CertificateMetadata {
+    pub title: String,
+    pub description: String,
+    pub achievement_type: String,
+    pub additional_data: BTreeMap<String, String>,
+}
+
+// Status of a certificate, as reported by the certification contract
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum CertificateStatus {
+    Active,
+    Suspended,
+    Withdrawn,
+    Expired,
+    Superseded,
+}
+
+// Certifying authority as returned by the authority contract's `get_authority`
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Authority {
+    pub authority_id: String,
+    pub name: String,
+    pub public_key: String,
+    pub registration_date: u64,
+    pub accreditation_info: String,
+    pub allowed_cert_types: Vec<String>,
+    pub status: AuthorityStatus,
+}
+
+// Status of a certifying authority
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum AuthorityStatus {
+    Active,
+    Inactive,
+}
+
+// Public details of the issuer that the contracts do not store
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct IssuerProfile {
+    pub id: Option<String>, // URI such as a did:web; defaults to the issuer's account
+    pub url: Option<String>,
+    pub email: Option<String>,
+}
+
+// The certification contract deployment a certificate was issued on
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct CertificationContract {
+    pub network: String, // "pubnet" or "testnet"
+    pub contract_id: String,
+}
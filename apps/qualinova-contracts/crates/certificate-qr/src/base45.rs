@@ -0,0 +1,61 @@
+use crate::Error;
+
+// Base45 alphabet (RFC 9285), which is the QR alphanumeric character set
+const ALPHABET: &[u8; 45] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ $%*+-./:";
+
+// Encode bytes as base45: every two bytes become three characters, and a
+// trailing byte becomes two
+pub fn encode(bytes: &[u8]) -> String {
+    let mut text = String::with_capacity(bytes.len().div_ceil(2) * 3);
+    
+    for chunk in bytes.chunks(2) {
+        let (mut value, digits) = match chunk {
+            [a, b] => ((*a as usize) << 8 | *b as usize, 3),
+            [a] => (*a as usize, 2),
+            _ => unreachable!(),
+        };
+        for _ in 0..digits {
+            text.push(ALPHABET[value % 45] as char);
+            value /= 45;
+        }
+    }
+    
+    text
+}
+
+// Decode base45 text
+pub fn decode(text: &str) -> Result<Vec<u8>, Error> {
+    let digits = text
+        .bytes()
+        .map(|c| {
+            ALPHABET
+                .iter()
+                .position(|&a| a == c)
+                .ok_or(Error::InvalidEncoding)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    
+    let mut bytes = Vec::with_capacity(digits.len() / 3 * 2 + 1);
+    for chunk in digits.chunks(3) {
+        match chunk {
+            [c, d, e] => {
+                let value = c + d * 45 + e * 45 * 45;
+                if value > 0xFFFF {
+                    return Err(Error::InvalidEncoding);
+                }
+                bytes.push((value >> 8) as u8);
+                bytes.push(value as u8);
+            }
+            [c, d] => {
+                let value = c + d * 45;
+                if value > 0xFF {
+                    return Err(Error::InvalidEncoding);
+                }
+                bytes.push(value as u8);
+            }
+            _ => return Err(Error::InvalidEncoding),
+        }
+    }
+    
+    Ok(bytes)
+}
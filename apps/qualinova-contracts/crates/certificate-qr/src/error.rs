@@ -0,0 +1,34 @@
+use std::fmt;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Error {
+    // The text is not valid base45
+    InvalidEncoding,
+    // The payload is not the length of its version
+    InvalidLength,
+    // The payload was encoded with a layout this version does not know
+    UnsupportedVersion(u8),
+    // The payload was corrupted
+    ChecksumMismatch,
+    // The contract ID is not a contract strkey
+    InvalidContractId,
+    // The payload does not fit in a QR code
+    QrCode,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidEncoding => write!(f, "invalid base45 encoding"),
+            Error::InvalidLength => write!(f, "invalid payload length"),
+            Error::UnsupportedVersion(version) => {
+                write!(f, "unsupported payload version {}", version)
+            }
+            Error::ChecksumMismatch => write!(f, "payload checksum mismatch"),
+            Error::InvalidContractId => write!(f, "invalid contract ID"),
+            Error::QrCode => write!(f, "payload does not fit in a QR code"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
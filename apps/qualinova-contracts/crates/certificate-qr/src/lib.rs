@@ -0,0 +1,14 @@
+// Off-chain encoding of the compact verification payloads printed as QR codes on
+// certificates, which the certification contract's `verify_qr_payload` checks
+
+mod base45;
+mod error;
+mod payload;
+mod qr;
+
+pub use error::Error;
+pub use payload::{network_id, Payload, PAYLOAD_LEN, PAYLOAD_VERSION};
+pub use qr::{qr_matrix, QrMatrix};
+
+#[cfg(test)]
+mod test;
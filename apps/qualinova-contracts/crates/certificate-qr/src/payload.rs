@@ -0,0 +1,99 @@
+use crate::{base45, Error};
+use sha2::{Digest, Sha256};
+use stellar_strkey::Contract;
+
+// Version of the payload layout
+pub const PAYLOAD_VERSION: u8 = 1;
+
+// Length of an encoded payload: the version, the certificate ID, the contract ID,
+// the network ID and the checksum
+pub const PAYLOAD_LEN: usize = 1 + 32 + 32 + 32 + CHECKSUM_LEN;
+
+// Length of the checksum, the start of the SHA-256 of the rest of the payload
+const CHECKSUM_LEN: usize = 4;
+
+// Verification payload of a certificate, as encoded in its QR code
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Payload {
+    pub certificate_id: [u8; 32],
+    pub contract_id: [u8; 32],
+    pub network_id: [u8; 32], // SHA-256 of the network passphrase
+}
+
+impl Payload {
+    // Create the payload of a certificate on a contract, given as a strkey, and the
+    // network with the passphrase
+    pub fn new(
+        certificate_id: [u8; 32],
+        contract_id: &str,
+        network_passphrase: &str,
+    ) -> Result<Self, Error> {
+        let contract = Contract::from_string(contract_id).map_err(|_| Error::InvalidContractId)?;
+        
+        Ok(Payload {
+            certificate_id,
+            contract_id: contract.0,
+            network_id: network_id(network_passphrase),
+        })
+    }
+    
+    // Get the contract ID as a strkey
+    pub fn contract_strkey(&self) -> String {
+        Contract(self.contract_id).to_string()
+    }
+    
+    // Encode the payload in binary
+    pub fn to_bytes(&self) -> [u8; PAYLOAD_LEN] {
+        let mut bytes = [0u8; PAYLOAD_LEN];
+        bytes[0] = PAYLOAD_VERSION;
+        bytes[1..33].copy_from_slice(&self.certificate_id);
+        bytes[33..65].copy_from_slice(&self.contract_id);
+        bytes[65..97].copy_from_slice(&self.network_id);
+        let checksum = checksum(&bytes[..97]);
+        bytes[97..].copy_from_slice(&checksum);
+        
+        bytes
+    }
+    
+    // Decode a binary payload, checking its version and checksum
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        match bytes.first() {
+            None => return Err(Error::InvalidLength),
+            Some(&PAYLOAD_VERSION) => {}
+            Some(&version) => return Err(Error::UnsupportedVersion(version)),
+        }
+        if bytes.len() != PAYLOAD_LEN {
+            return Err(Error::InvalidLength);
+        }
+        if checksum(&bytes[..97]) != bytes[97..] {
+            return Err(Error::ChecksumMismatch);
+        }
+        
+        Ok(Payload {
+            certificate_id: bytes[1..33].try_into().unwrap(),
+            contract_id: bytes[33..65].try_into().unwrap(),
+            network_id: bytes[65..97].try_into().unwrap(),
+        })
+    }
+    
+    // Encode the payload as base45 text, which fits the QR alphanumeric mode
+    pub fn encode(&self) -> String {
+        base45::encode(&self.to_bytes())
+    }
+    
+    // Decode a payload from base45 text
+    pub fn decode(text: &str) -> Result<Self, Error> {
+        Self::from_bytes(&base45::decode(text)?)
+    }
+}
+
+// Get the network ID of a network passphrase
+pub fn network_id(network_passphrase: &str) -> [u8; 32] {
+    Sha256::digest(network_passphrase.as_bytes()).into()
+}
+
+// Get the checksum of the payload bytes before it
+fn checksum(bytes: &[u8]) -> [u8; CHECKSUM_LEN] {
+    let hash = Sha256::digest(bytes);
+    hash[..CHECKSUM_LEN].try_into().unwrap()
+}
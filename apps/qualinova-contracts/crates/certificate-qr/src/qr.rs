@@ -0,0 +1,32 @@
+use crate::{Error, Payload};
+use qrcode::{Color, EcLevel, QrCode};
+
+// Square matrix of QR code modules, without the quiet zone
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct QrMatrix {
+    pub width: usize,
+    pub modules: Vec<bool>, // row by row, true for dark modules
+}
+
+impl QrMatrix {
+    // Check whether the module in column `x` of row `y` is dark
+    pub fn is_dark(&self, x: usize, y: usize) -> bool {
+        self.modules[y * self.width + x]
+    }
+}
+
+// Generate the QR code of a payload. The base45 text is encoded in alphanumeric
+// mode with medium error correction, so printed codes survive some damage.
+pub fn qr_matrix(payload: &Payload) -> Result<QrMatrix, Error> {
+    let code = QrCode::with_error_correction_level(payload.encode(), EcLevel::M)
+        .map_err(|_| Error::QrCode)?;
+    
+    Ok(QrMatrix {
+        width: code.width(),
+        modules: code
+            .to_colors()
+            .into_iter()
+            .map(|color| color == Color::Dark)
+            .collect(),
+    })
+}
@@ -0,0 +1,84 @@
+use crate::*;
+use stellar_strkey::Contract;
+
+const TESTNET_PASSPHRASE: &str = "Test SDF Network ; September 2015";
+
+fn create_payload() -> Payload {
+    let contract_id = Contract([7u8; 32]).to_string();
+    Payload::new([1u8; 32], &contract_id, TESTNET_PASSPHRASE).unwrap()
+}
+
+#[test]
+fn test_base45() {
+    // Test vectors from RFC 9285
+    assert_eq!(base45::encode(b"AB"), "BB8");
+    assert_eq!(base45::encode(b"Hello!!"), "%69 VD92EX0");
+    assert_eq!(base45::encode(b"base-45"), "UJCLQE7W581");
+    assert_eq!(base45::decode("QED8WEX0").unwrap(), b"ietf!");
+    
+    assert_eq!(base45::decode("GGW"), Err(Error::InvalidEncoding));
+    assert_eq!(base45::decode("ab"), Err(Error::InvalidEncoding));
+    assert_eq!(base45::decode("BB8B"), Err(Error::InvalidEncoding));
+}
+
+#[test]
+fn test_payload_round_trip() {
+    let payload = create_payload();
+    assert_eq!(payload.contract_id, [7u8; 32]);
+    assert_eq!(payload.contract_strkey(), Contract([7u8; 32]).to_string());
+    assert_eq!(payload.network_id, network_id(TESTNET_PASSPHRASE));
+    
+    let bytes = payload.to_bytes();
+    assert_eq!(bytes.len(), PAYLOAD_LEN);
+    assert_eq!(bytes[0], PAYLOAD_VERSION);
+    assert_eq!(Payload::from_bytes(&bytes).unwrap(), payload);
+    
+    let text = payload.encode();
+    assert_eq!(text.len(), 152);
+    assert_eq!(Payload::decode(&text).unwrap(), payload);
+}
+
+#[test]
+fn test_payload_errors() {
+    let bytes = create_payload().to_bytes();
+    
+    let mut corrupted = bytes;
+    corrupted[10] ^= 1;
+    assert_eq!(
+        Payload::from_bytes(&corrupted),
+        Err(Error::ChecksumMismatch)
+    );
+    
+    let mut future = bytes;
+    future[0] = 2;
+    assert_eq!(
+        Payload::from_bytes(&future),
+        Err(Error::UnsupportedVersion(2))
+    );
+    
+    assert_eq!(
+        Payload::from_bytes(&bytes[..100]),
+        Err(Error::InvalidLength)
+    );
+    assert_eq!(Payload::from_bytes(&[]), Err(Error::InvalidLength));
+    
+    assert_eq!(
+        Payload::new([1u8; 32], "GBNOTACONTRACT", TESTNET_PASSPHRASE),
+        Err(Error::InvalidContractId)
+    );
+}
+
+#[test]
+fn test_qr_matrix() {
+    let matrix = qr_matrix(&create_payload()).unwrap();
+    
+    // 152 alphanumeric characters need a version 6 code at medium error correction
+    assert_eq!(matrix.width, 41);
+    assert_eq!(matrix.modules.len(), 41 * 41);
+    
+    // Finder pattern in the top-left corner
+    assert!(matrix.is_dark(0, 0));
+    assert!(matrix.is_dark(6, 6));
+    assert!(!matrix.is_dark(1, 1));
+    assert!(matrix.is_dark(2, 2));
+}